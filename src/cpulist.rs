@@ -0,0 +1,406 @@
+use std::fmt::Write as _;
+use std::ops::RangeInclusive;
+
+use crate::{Fragment, RangeError, RangeResult, RangeSet};
+
+/// The token which stands for the last bit of the bitmap (e.g. `0-N`)
+const LAST_BIT: &str = "N";
+/// The region which stands for all the bits of the bitmap, regardless of case (e.g. `all:1/2`)
+const ALL_BITS: &str = "all";
+/// The amount of bits of a hex cpumask word
+const WORD_BITS: usize = 32;
+
+/// Parse a Linux cpulist (e.g. `0-3,8-11`) into a [`RangeSet`], as the kernel `bitmap_parselist` does.
+///
+/// This is the format of `/sys/devices/system/cpu/online`, the cgroup `cpuset.cpus` and the `taskset -c`
+/// argument. Regions are separated by commas or whitespace and can be:
+///
+/// - a single bit (e.g. `5`)
+/// - a range of bits (e.g. `0-3`)
+/// - a range split into groups of `group` bits, where only the first `used` bits of each group are set
+///   (e.g. `0-15:2/4` is `0,1,4,5,8,9,12,13`)
+/// - all the bits (`all`, regardless of case), which can be split into groups too (e.g. `all:1/2`)
+///
+/// `N` stands for the last bit of the bitmap (`nbits - 1`). Parsing stops at the first newline and an empty list
+/// is an empty set.
+///
+/// # Arguments
+/// - list: &str - the cpulist
+/// - nbits: usize - the amount of bits of the bitmap (e.g. the amount of possible CPUs)
+///
+/// # Returns
+/// - Result<RangeSet<usize>, RangeError> - the set bits.
+///
+/// Bits which don't fit in the bitmap are reported as [`RangeError::OutOfBounds`].
+///
+/// # Example
+///
+/// ```rust
+/// let cpus = range_parser::parse_cpulist("0-15:2/4\n", 16).unwrap();
+/// assert_eq!(cpus.intervals(), &[0..=1, 4..=5, 8..=9, 12..=13]);
+///
+/// let cpus = range_parser::parse_cpulist("0,8-N", 12).unwrap();
+/// assert_eq!(cpus.intervals(), &[0..=0, 8..=11]);
+/// ```
+pub fn parse_cpulist(list: &str, nbits: usize) -> RangeResult<RangeSet<usize>> {
+    let mut intervals = Vec::new();
+    for (index, (offset, region)) in regions(first_line(list)).enumerate() {
+        intervals
+            .extend(parse_region(region, nbits).map_err(|err| err.locate(region, offset, index))?);
+    }
+
+    Ok(RangeSet::from_intervals(intervals))
+}
+
+/// Format a [`RangeSet`] as a Linux cpulist (e.g. `0-3,8-11\n`), as the kernel `bitmap_print_to_pagebuf` does.
+///
+/// Consecutive bits are formatted as ranges and the list ends with a newline, as in sysfs files.
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::RangeSet;
+///
+/// let cpus = RangeSet::from_intervals([0..=3, 8..=11, 15..=15]);
+/// assert_eq!(range_parser::format_cpulist(&cpus), "0-3,8-11,15\n");
+/// assert_eq!(range_parser::format_cpulist(&RangeSet::new()), "\n");
+/// ```
+pub fn format_cpulist(set: &RangeSet<usize>) -> String {
+    format!("{set}\n")
+}
+
+/// Parse a Linux hex cpumask (e.g. `00000000,0000ff0f`) into a [`RangeSet`], as the kernel `bitmap_parse` does.
+///
+/// The mask is made of words of up to 8 hex digits separated by commas, each one holding 32 bits, with the most
+/// significant word first. Parsing stops at the first newline.
+///
+/// # Arguments
+/// - mask: &str - the hex cpumask
+/// - nbits: usize - the amount of bits of the bitmap (e.g. the amount of possible CPUs)
+///
+/// # Returns
+/// - Result<RangeSet<usize>, RangeError> - the set bits.
+///
+/// Words with more than 8 digits and bits which don't fit in the bitmap are reported as
+/// [`RangeError::OutOfBounds`].
+///
+/// # Example
+///
+/// ```rust
+/// let cpus = range_parser::parse_cpumask("1,0000ff0f\n", 64).unwrap();
+/// assert_eq!(cpus.intervals(), &[0..=3, 8..=15, 32..=32]);
+/// ```
+pub fn parse_cpumask(mask: &str, nbits: usize) -> RangeResult<RangeSet<usize>> {
+    let mask = first_line(mask);
+    // the kernel reads the last word first, so a trailing separator is not a word
+    if mask.ends_with(is_region_separator) {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(mask)));
+    }
+
+    let words: Vec<(usize, &str)> = regions(mask).collect();
+    let mut bits = Vec::new();
+    for (index, (offset, word)) in words.iter().rev().enumerate() {
+        let value = parse_word(word, index, nbits)
+            .map_err(|err| err.locate(word, *offset, words.len() - index - 1))?;
+        bits.extend(
+            (0..WORD_BITS)
+                .filter(|bit| value & (1 << bit) != 0)
+                .map(|bit| {
+                    let bit = index * WORD_BITS + bit;
+                    bit..=bit
+                }),
+        );
+    }
+
+    Ok(RangeSet::from_intervals(bits))
+}
+
+/// Format a [`RangeSet`] as a Linux hex cpumask (e.g. `ff,ffffffff\n`), as the kernel `bitmap_print_to_pagebuf`
+/// does.
+///
+/// The mask is made of 32 bits words separated by commas, with the most significant word first. Words are
+/// zero-padded to 8 hex digits, except for the first one which is only as wide as the bits it holds, and the mask
+/// ends with a newline, as in sysfs files.
+///
+/// Bits which don't fit in the bitmap of `nbits` bits are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::RangeSet;
+///
+/// let cpus = RangeSet::from_intervals([0..=3, 8..=15, 32..=32]);
+/// assert_eq!(range_parser::format_cpumask(&cpus, 40), "01,0000ff0f\n");
+/// assert_eq!(range_parser::format_cpumask(&cpus, 8), "0f\n");
+/// ```
+pub fn format_cpumask(set: &RangeSet<usize>, nbits: usize) -> String {
+    let mut mask = String::new();
+    for index in (0..nbits.div_ceil(WORD_BITS)).rev() {
+        let word_bits = (nbits - index * WORD_BITS).min(WORD_BITS);
+        let value = (0..word_bits)
+            .filter(|bit| set.contains(&(index * WORD_BITS + bit)))
+            .fold(0u32, |value, bit| value | (1 << bit));
+        if !mask.is_empty() {
+            mask.push(',');
+        }
+        let _ = write!(mask, "{value:0width$x}", width = word_bits.div_ceil(4));
+    }
+    mask.push('\n');
+
+    mask
+}
+
+/// Returns the input up to the first newline, where the kernel stops parsing
+fn first_line(input: &str) -> &str {
+    input.split('\n').next().unwrap_or_default()
+}
+
+/// Returns whether `c` separates the regions of a cpulist or the words of a cpumask
+fn is_region_separator(c: char) -> bool {
+    c == ',' || c.is_ascii_whitespace()
+}
+
+/// Iterate over the non-empty regions of `input`, with their offset
+fn regions(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .split(is_region_separator)
+        .scan(0, |offset, region| {
+            let region_offset = *offset;
+            *offset += region.len() + 1;
+            Some((region_offset, region))
+        })
+        .filter(|(_, region)| !region.is_empty())
+}
+
+/// Parse a cpulist region (e.g. `5`, `0-3`, `0-15:2/4` or `all`) into the intervals of the set bits
+fn parse_region(region: &str, nbits: usize) -> RangeResult<Vec<RangeInclusive<usize>>> {
+    let (range, pattern) = match region.split_once(':') {
+        Some((range, pattern)) => (range, Some(pattern)),
+        None => (region, None),
+    };
+    let (start, last, end) = if range.eq_ignore_ascii_case(ALL_BITS) {
+        let last = nbits
+            .checked_sub(1)
+            .ok_or_else(|| RangeError::OutOfBounds(Fragment::unlocated(range)))?;
+        (0, last, range)
+    } else {
+        let (start, end) = match (range.split_once('-'), pattern) {
+            (Some((start, end)), _) => (start, end),
+            // a pattern requires a range
+            (None, Some(_)) => {
+                return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(region)))
+            }
+            (None, None) => (range, range),
+        };
+        (parse_bit(start, nbits)?, parse_bit(end, nbits)?, end)
+    };
+    if start > last {
+        return Err(RangeError::StartBiggerThanEnd(Fragment::unlocated(range)));
+    }
+
+    let Some(pattern) = pattern else {
+        return check_bit(last, end, nbits).map(|_| vec![start..=last]);
+    };
+    let Some((used, group)) = pattern.split_once('/') else {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(pattern)));
+    };
+    let used = parse_bit(used, nbits)?;
+    let group = parse_bit(group, nbits)?;
+    if group == 0 {
+        return Err(RangeError::ZeroStep(Fragment::unlocated(pattern)));
+    }
+    if used > group {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(pattern)));
+    }
+    check_bit(last, end, nbits)?;
+
+    // whole groups are contiguous
+    if used == group {
+        return Ok(vec![start..=last]);
+    }
+    Ok((start..=last)
+        .step_by(group)
+        .filter(|_| used > 0)
+        .map(|group_start| group_start..=group_start.saturating_add(used - 1).min(last))
+        .collect())
+}
+
+/// Parse a decimal number of a cpulist region, or `N` for the last bit of the bitmap
+fn parse_bit(text: &str, nbits: usize) -> RangeResult<usize> {
+    if text == LAST_BIT {
+        return nbits
+            .checked_sub(1)
+            .ok_or_else(|| RangeError::OutOfBounds(Fragment::unlocated(text)));
+    }
+    if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(RangeError::NotANumber(Fragment::unlocated(text)));
+    }
+
+    text.parse()
+        .map_err(|_| RangeError::OutOfBounds(Fragment::unlocated(text)))
+}
+
+/// Check that `bit`, parsed from `text`, fits in a bitmap of `nbits` bits
+fn check_bit(bit: usize, text: &str, nbits: usize) -> RangeResult<()> {
+    if bit >= nbits {
+        return Err(RangeError::OutOfBounds(Fragment::unlocated(text)));
+    }
+
+    Ok(())
+}
+
+/// Parse the `index`-th word of a cpumask, counting from the least significant one
+fn parse_word(word: &str, index: usize, nbits: usize) -> RangeResult<u32> {
+    if !word.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(RangeError::NotANumber(Fragment::unlocated(word)));
+    }
+    let value = u32::from_str_radix(word, 16)
+        .ok()
+        .filter(|_| word.len() <= WORD_BITS / 4)
+        .ok_or_else(|| RangeError::OutOfBounds(Fragment::unlocated(word)))?;
+    // bits beyond the end of the bitmap must not be set
+    let available = nbits.saturating_sub(index * WORD_BITS).min(WORD_BITS);
+    if available < WORD_BITS && value >> available != 0 {
+        return Err(RangeError::OutOfBounds(Fragment::unlocated(word)));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_cpulist() {
+        assert_eq!(
+            parse_cpulist("0-3,8-11\n", 16).unwrap().intervals(),
+            &[0..=3, 8..=11]
+        );
+        assert_eq!(
+            parse_cpulist("0-1023:2/256", 1024).unwrap().intervals(),
+            &[0..=1, 256..=257, 512..=513, 768..=769]
+        );
+        assert_eq!(
+            parse_cpulist("0-10:3/4", 16).unwrap().intervals(),
+            &[0..=2, 4..=6, 8..=10]
+        );
+        assert_eq!(
+            parse_cpulist("2-N:4/4,0", 8).unwrap().intervals(),
+            &[0..=0, 2..=7]
+        );
+        assert_eq!(parse_cpulist("0-7:0/4", 8).unwrap(), RangeSet::new());
+    }
+
+    #[test]
+    fn should_parse_all_bits() {
+        assert_eq!(parse_cpulist("all", 4).unwrap().intervals(), &[0..=3]);
+        assert_eq!(parse_cpulist("ALL\n", 4).unwrap().intervals(), &[0..=3]);
+        assert_eq!(
+            parse_cpulist("All:1/2", 8).unwrap().intervals(),
+            &[0..=0, 2..=2, 4..=4, 6..=6]
+        );
+        assert_eq!(
+            parse_cpulist("all:3/4", 10).unwrap().intervals(),
+            &[0..=2, 4..=6, 8..=9]
+        );
+        assert_eq!(
+            parse_cpulist("all", 0).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("all", 0..3, 0))
+        );
+        assert!(parse_cpulist("all-3", 8).is_err());
+        assert!(parse_cpulist("allx", 8).is_err());
+        assert!(parse_cpulist("all:1", 8).is_err());
+    }
+
+    #[test]
+    fn should_parse_empty_cpulist() {
+        assert!(parse_cpulist("", 8).unwrap().is_empty());
+        assert!(parse_cpulist("\n", 8).unwrap().is_empty());
+        assert_eq!(
+            parse_cpulist(" ,1 3,\n7", 8).unwrap().intervals(),
+            &[1..=1, 3..=3]
+        );
+    }
+
+    #[test]
+    fn should_not_parse_invalid_cpulist() {
+        assert_eq!(
+            parse_cpulist("0-3,5-4", 8).unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("5-4", 4..7, 1))
+        );
+        assert_eq!(
+            parse_cpulist("0-3 8", 8).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("8", 4..5, 1))
+        );
+        assert_eq!(
+            parse_cpulist("0-x", 8).unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 2..3, 0))
+        );
+        assert_eq!(
+            parse_cpulist("0-7:1/0", 8).unwrap_err(),
+            RangeError::ZeroStep(Fragment::new("1/0", 4..7, 0))
+        );
+        assert!(parse_cpulist("0-7:5/4", 8).is_err());
+        assert!(parse_cpulist("0-7:1", 8).is_err());
+        assert!(parse_cpulist("3:1/2", 8).is_err());
+        assert!(parse_cpulist("0 -3", 8).is_err());
+        assert!(parse_cpulist("-1", 8).is_err());
+        assert!(parse_cpulist("N", 0).is_err());
+        // only ASCII whitespace separates regions, as for the kernel
+        assert_eq!(
+            parse_cpulist("1\u{a0}2", 8).unwrap_err(),
+            RangeError::NotANumber(Fragment::new("1\u{a0}2", 0..4, 0))
+        );
+    }
+
+    #[test]
+    fn should_format_cpulist() {
+        let cpus = parse_cpulist("0-15:2/4,16,17", 32).unwrap();
+        assert_eq!(format_cpulist(&cpus), "0-1,4-5,8-9,12-13,16-17\n");
+        assert_eq!(parse_cpulist(&format_cpulist(&cpus), 32).unwrap(), cpus);
+    }
+
+    #[test]
+    fn should_parse_cpumask() {
+        assert_eq!(parse_cpumask("ff\n", 8).unwrap().intervals(), &[0..=7]);
+        assert_eq!(
+            parse_cpumask("00000001,80000000", 64).unwrap().intervals(),
+            &[31..=32]
+        );
+        assert_eq!(parse_cpumask(",1,,0", 64).unwrap().intervals(), &[32..=32]);
+        assert!(parse_cpumask("0", 8).unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_not_parse_invalid_cpumask() {
+        assert_eq!(
+            parse_cpumask("ff,0000000g", 64).unwrap_err(),
+            RangeError::NotANumber(Fragment::new("0000000g", 3..11, 1))
+        );
+        assert_eq!(
+            parse_cpumask("1ff", 8).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("1ff", 0..3, 0))
+        );
+        assert_eq!(
+            parse_cpumask("1,00000000", 32).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("1", 0..1, 0))
+        );
+        assert!(parse_cpumask("000000000", 64).is_err());
+        assert!(parse_cpumask("ff,", 64).is_err());
+    }
+
+    #[test]
+    fn should_format_cpumask() {
+        let cpus = RangeSet::from_intervals([0..=3, 36..=39, 100..=100]);
+        assert_eq!(format_cpumask(&cpus, 72), "00,000000f0,0000000f\n");
+        assert_eq!(format_cpumask(&cpus, 33), "0,0000000f\n");
+        assert_eq!(format_cpumask(&cpus, 0), "\n");
+        assert_eq!(
+            parse_cpumask(&format_cpumask(&cpus, 72), 72).unwrap(),
+            RangeSet::from_intervals([0..=3, 36..=39])
+        );
+    }
+}
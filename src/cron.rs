@@ -0,0 +1,399 @@
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+
+use crate::cyclic::{MONTH_ABBREVIATIONS, WEEKDAY_ABBREVIATIONS};
+use crate::{Fragment, RangeError, RangeParser, RangeResult, RangeSet};
+
+/// The characters allowed in a cron field, besides digits and names
+const CRON_SYNTAX: &str = "*,-/";
+
+/// A field of a cron expression
+///
+/// Each field is a list of values or ranges (e.g. `1-5,10`), where `*` is the whole domain of the field and a
+/// range can have a step after `/` (e.g. `*/15`). Months and days of week can also be named, case-insensitively
+/// (e.g. `JAN,MAR` or `mon-fri`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CronField {
+    /// Minutes, from 0 to 59
+    Minute,
+    /// Hours, from 0 to 23
+    Hour,
+    /// Days of month, from 1 to 31
+    DayOfMonth,
+    /// Months, from 1 (`JAN`) to 12 (`DEC`)
+    Month,
+    /// Days of week, from 0 (`SUN`) to 6 (`SAT`), where 7 is Sunday too
+    DayOfWeek,
+}
+
+impl CronField {
+    /// Returns the values accepted by the field
+    pub fn domain(&self) -> RangeInclusive<u8> {
+        match self {
+            Self::Minute => 0..=59,
+            Self::Hour => 0..=23,
+            Self::DayOfMonth => 1..=31,
+            Self::Month => 1..=12,
+            Self::DayOfWeek => 0..=7,
+        }
+    }
+
+    /// Returns the names which can be used instead of the values of the field, starting from the first value of
+    /// the domain
+    pub fn names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Month => MONTH_ABBREVIATIONS,
+            Self::DayOfWeek => WEEKDAY_ABBREVIATIONS,
+            Self::Minute | Self::Hour | Self::DayOfMonth => &[],
+        }
+    }
+
+    /// Parse a cron field (e.g. `*/15` or `MON-FRI`) into the set of matching values
+    ///
+    /// Errors are wrapped in [`RangeError::InvalidCronField`], which reports the field, while the fragment
+    /// reports the failing segment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::CronField;
+    ///
+    /// let minutes = CronField::Minute.parse("*/15").unwrap();
+    /// assert_eq!(minutes.iter().collect::<Vec<u8>>(), vec![0, 15, 30, 45]);
+    ///
+    /// let days = CronField::DayOfWeek.parse("MON-FRI").unwrap();
+    /// assert_eq!(days.intervals(), &[1..=5]);
+    ///
+    /// let error = CronField::Hour.parse("0,24").unwrap_err();
+    /// assert_eq!(error.to_string(), "Invalid hour field: Value out of bounds: 24");
+    /// assert_eq!(error.segment(), Some(1));
+    /// ```
+    pub fn parse(&self, field: &str) -> RangeResult<RangeSet<u8>> {
+        self.parse_at(field, 0)
+    }
+
+    /// Parse a cron field which starts at `offset` in the cron expression
+    fn parse_at(&self, field: &str, offset: usize) -> RangeResult<RangeSet<u8>> {
+        self.parse_values(field, offset)
+            .map_err(|err| RangeError::InvalidCronField(*self, Box::new(err)))
+    }
+
+    /// Parse each segment of the field, locating errors from `offset`
+    fn parse_values(&self, field: &str, mut offset: usize) -> RangeResult<RangeSet<u8>> {
+        // '*' is the whole domain, so it can't be a factor separator too
+        let parser = RangeParser::new().step_separator("/").factor_separator("");
+        let domain = self.domain();
+        let mut values = Vec::new();
+        for (index, segment) in field.split(',').enumerate() {
+            let segment_values = self
+                .parse_segment(&parser, segment, &domain)
+                .map_err(|err| err.locate(segment, offset, index))?;
+            values.extend(segment_values);
+            offset += segment.len() + 1;
+        }
+        // Sunday is both 0 and 7
+        if *self == Self::DayOfWeek {
+            values
+                .iter_mut()
+                .filter(|value| **value == 7)
+                .for_each(|value| *value = 0);
+        }
+
+        Ok(values.into_iter().collect())
+    }
+
+    /// Parse a segment of the field (e.g. `1-5/2`) into its values
+    fn parse_segment(
+        &self,
+        parser: &RangeParser,
+        segment: &str,
+        domain: &RangeInclusive<u8>,
+    ) -> RangeResult<Vec<u8>> {
+        if let Some(invalid) = segment
+            .split(|c: char| c.is_ascii_alphanumeric() || CRON_SYNTAX.contains(c))
+            .find(|text| !text.is_empty())
+        {
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(invalid)));
+        }
+        // ranges can't be open-ended
+        let range = segment.split('/').next().unwrap_or_default();
+        if range.starts_with('-') || range.ends_with('-') {
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(segment)));
+        }
+
+        let (replaced, origins) = self.replace_names(segment)?;
+        parser
+            .parse_bounded(&replaced, *domain.start(), *domain.end())
+            .map_err(|err| match err.span() {
+                // report the text of the segment, rather than the one with the names replaced
+                Some(span) if !span.is_empty() && span.end <= origins.len() => {
+                    let origin = origins[span.start].start..origins[span.end - 1].end;
+                    err.with_text(&segment[origin])
+                }
+                _ => err,
+            })
+    }
+
+    /// Replace the names in `segment` with their values.
+    ///
+    /// Returns the replaced segment, along with the span in `segment` of each of its bytes.
+    fn replace_names(&self, segment: &str) -> RangeResult<(String, Vec<Range<usize>>)> {
+        let mut replaced = String::with_capacity(segment.len());
+        let mut origins = Vec::with_capacity(segment.len());
+        let mut rest = segment;
+        while let Some(name_start) = rest.find(|c: char| c.is_ascii_alphabetic()) {
+            let (before, name) = rest.split_at(name_start);
+            let name_end = name
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(name.len());
+            let (name, after) = name.split_at(name_end);
+            let index = self
+                .names()
+                .iter()
+                .position(|known| known.eq_ignore_ascii_case(name))
+                .ok_or_else(|| RangeError::NotANumber(Fragment::unlocated(name)))?;
+            let before_start = segment.len() - rest.len();
+            let name_start = before_start + before.len();
+            let value = (usize::from(*self.domain().start()) + index).to_string();
+            replaced.push_str(before);
+            origins.extend((before_start..name_start).map(|index| index..index + 1));
+            replaced.push_str(&value);
+            origins.extend(value.bytes().map(|_| name_start..name_start + name.len()));
+            rest = after;
+        }
+        let rest_start = segment.len() - rest.len();
+        replaced.push_str(rest);
+        origins.extend((rest_start..segment.len()).map(|index| index..index + 1));
+
+        Ok((replaced, origins))
+    }
+}
+
+impl fmt::Display for CronField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Minute => write!(f, "minute"),
+            Self::Hour => write!(f, "hour"),
+            Self::DayOfMonth => write!(f, "day of month"),
+            Self::Month => write!(f, "month"),
+            Self::DayOfWeek => write!(f, "day of week"),
+        }
+    }
+}
+
+/// A cron expression made of five whitespace-separated fields (e.g. `*/15 9-17 * * MON-FRI`), expanded into the
+/// values matching each field
+///
+/// See [`CronField`] for the syntax of the fields.
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::CronSchedule;
+///
+/// let schedule: CronSchedule = "*/15 9-17 1,15 JAN-MAR mon-fri".parse().unwrap();
+/// assert!(schedule.minutes().contains(&45));
+/// assert_eq!(schedule.hours().intervals(), &[9..=17]);
+/// assert_eq!(schedule.days_of_month().intervals(), &[1..=1, 15..=15]);
+/// assert_eq!(schedule.months().intervals(), &[1..=3]);
+/// assert_eq!(schedule.days_of_week().intervals(), &[1..=5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CronSchedule {
+    minutes: RangeSet<u8>,
+    hours: RangeSet<u8>,
+    days_of_month: RangeSet<u8>,
+    months: RangeSet<u8>,
+    days_of_week: RangeSet<u8>,
+}
+
+impl CronSchedule {
+    /// Returns the minutes matching the schedule
+    pub fn minutes(&self) -> &RangeSet<u8> {
+        &self.minutes
+    }
+
+    /// Returns the hours matching the schedule
+    pub fn hours(&self) -> &RangeSet<u8> {
+        &self.hours
+    }
+
+    /// Returns the days of month matching the schedule
+    pub fn days_of_month(&self) -> &RangeSet<u8> {
+        &self.days_of_month
+    }
+
+    /// Returns the months matching the schedule
+    pub fn months(&self) -> &RangeSet<u8> {
+        &self.months
+    }
+
+    /// Returns the days of week matching the schedule, where Sunday is 0
+    pub fn days_of_week(&self) -> &RangeSet<u8> {
+        &self.days_of_week
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<(usize, &str)> = s
+            .split(char::is_whitespace)
+            .scan(0, |offset, field| {
+                let field_offset = *offset;
+                *offset += field.len() + 1;
+                Some((field_offset, field))
+            })
+            .filter(|(_, field)| !field.is_empty())
+            .collect();
+        let Ok([minute, hour, day_of_month, month, day_of_week]) =
+            <[(usize, &str); 5]>::try_from(fields)
+        else {
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(s)));
+        };
+
+        Ok(Self {
+            minutes: CronField::Minute.parse_at(minute.1, minute.0)?,
+            hours: CronField::Hour.parse_at(hour.1, hour.0)?,
+            days_of_month: CronField::DayOfMonth.parse_at(day_of_month.1, day_of_month.0)?,
+            months: CronField::Month.parse_at(month.1, month.0)?,
+            days_of_week: CronField::DayOfWeek.parse_at(day_of_week.1, day_of_week.0)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn values(set: RangeSet<u8>) -> Vec<u8> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn should_parse_cron_fields() {
+        assert_eq!(
+            values(CronField::Minute.parse("*/20").unwrap()),
+            vec![0, 20, 40]
+        );
+        assert_eq!(
+            values(CronField::Hour.parse("1-5/2,23").unwrap()),
+            vec![1, 3, 5, 23]
+        );
+        assert_eq!(
+            CronField::DayOfMonth.parse("*").unwrap().intervals(),
+            &[1..=31]
+        );
+        assert_eq!(
+            values(CronField::Minute.parse("5,5,0").unwrap()),
+            vec![0, 5]
+        );
+    }
+
+    #[test]
+    fn should_parse_names_case_insensitively() {
+        assert_eq!(
+            values(CronField::Month.parse("JAN,mar,Oct-dec/2").unwrap()),
+            vec![1, 3, 10, 12]
+        );
+        assert_eq!(
+            values(CronField::DayOfWeek.parse("sun,WED-fri").unwrap()),
+            vec![0, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn should_treat_seven_as_sunday() {
+        assert_eq!(
+            values(CronField::DayOfWeek.parse("5-7").unwrap()),
+            vec![0, 5, 6]
+        );
+        assert_eq!(
+            CronField::DayOfWeek.parse("*").unwrap().intervals(),
+            &[0..=6]
+        );
+    }
+
+    #[test]
+    fn should_report_field_and_segment() {
+        assert_eq!(
+            CronField::Minute.parse("0,60").unwrap_err(),
+            RangeError::InvalidCronField(
+                CronField::Minute,
+                Box::new(RangeError::OutOfBounds(Fragment::new("60", 2..4, 1)))
+            )
+        );
+        assert_eq!(
+            CronField::DayOfWeek.parse("MON,FOO").unwrap_err(),
+            RangeError::InvalidCronField(
+                CronField::DayOfWeek,
+                Box::new(RangeError::NotANumber(Fragment::new("FOO", 4..7, 1)))
+            )
+        );
+        let error = CronField::Month.parse("1,DEC-JAN/2").unwrap_err();
+        assert_eq!(
+            error,
+            RangeError::InvalidCronField(
+                CronField::Month,
+                Box::new(RangeError::StartBiggerThanEnd(Fragment::new(
+                    "DEC-JAN",
+                    2..9,
+                    1
+                )))
+            )
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid month field: Start of the range cannot be bigger than the end: DEC-JAN"
+        );
+        assert_eq!(
+            CronField::DayOfWeek.parse("0,mon-fri/0").unwrap_err(),
+            RangeError::InvalidCronField(
+                CronField::DayOfWeek,
+                Box::new(RangeError::ZeroStep(Fragment::new("mon-fri/0", 2..11, 1)))
+            )
+        );
+    }
+
+    #[test]
+    fn should_not_parse_invalid_cron_fields() {
+        assert!(CronField::Minute.parse("").is_err());
+        assert!(CronField::Minute.parse("*/0").is_err());
+        assert!(CronField::Minute.parse("5-").is_err());
+        assert!(CronField::Minute.parse("-5").is_err());
+        assert!(CronField::Minute.parse("1 ,2").is_err());
+        assert!(CronField::Minute.parse("!5").is_err());
+        assert!(CronField::Minute.parse("1-50*2").is_err());
+        assert!(CronField::Minute.parse("*2").is_err());
+        assert!(CronField::Hour.parse("MON").is_err());
+        assert!(CronField::DayOfMonth.parse("0").is_err());
+    }
+
+    #[test]
+    fn should_parse_cron_schedule() {
+        let schedule: CronSchedule = " 0  0 * * 7\n".parse().unwrap();
+        assert_eq!(schedule.minutes().intervals(), &[0..=0]);
+        assert_eq!(schedule.months().intervals(), &[1..=12]);
+        assert_eq!(schedule.days_of_week().intervals(), &[0..=0]);
+    }
+
+    #[test]
+    fn should_locate_errors_in_cron_schedule() {
+        let expression = "*/5 8-18 * 13 *";
+        let error = expression.parse::<CronSchedule>().unwrap_err();
+        assert_eq!(
+            error,
+            RangeError::InvalidCronField(
+                CronField::Month,
+                Box::new(RangeError::OutOfBounds(Fragment::new("13", 11..13, 0)))
+            )
+        );
+        assert_eq!(error.render(expression), "*/5 8-18 * 13 *\n           ^^");
+        assert!("* * * *".parse::<CronSchedule>().is_err());
+        assert!("* * * * * *".parse::<CronSchedule>().is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use std::ops::RangeInclusive;
+
+use crate::lexer::Syntax;
+use crate::parser::{parse_as_t, WILDCARD};
+use crate::{Fragment, RangeError, RangeParser, RangeResult};
+
+/// English names of the days of week, starting from Sunday
+pub(crate) const WEEKDAY_NAMES: &[&str] = &[
+    "sunday",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+];
+/// English abbreviations of the days of week, starting from Sunday
+pub(crate) const WEEKDAY_ABBREVIATIONS: &[&str] =
+    &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+/// English names of the months, starting from January
+pub(crate) const MONTH_NAMES: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+/// English abbreviations of the months, starting from January
+pub(crate) const MONTH_ABBREVIATIONS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// A cyclic domain of values, such as the days of week or the months, where a range going "backwards" wraps around
+/// the end of the domain (e.g. `fri-mon` is `fri,sat,sun,mon`) instead of failing with
+/// [`RangeError::StartBiggerThanEnd`].
+///
+/// Ranges are made of values or names, which are matched case-insensitively, and can have a step
+/// (e.g. `mon-sun:2`), while `*` is the whole domain.
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::CyclicDomain;
+///
+/// let weekdays = CyclicDomain::weekdays();
+/// assert_eq!(weekdays.parse("fri-mon").unwrap(), vec![5, 6, 0, 1]);
+///
+/// let months = CyclicDomain::months();
+/// assert_eq!(months.parse("Nov-February").unwrap(), vec![11, 12, 1, 2]);
+///
+/// let hours = CyclicDomain::new(0, 23);
+/// assert_eq!(hours.parse("22-2:2").unwrap(), vec![22, 0, 2]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicDomain {
+    first: u32,
+    last: u32,
+    names: Vec<(String, u32)>,
+}
+
+impl CyclicDomain {
+    /// Create a new [`CyclicDomain`] from `first` to `last`, without names
+    ///
+    /// If `first` is bigger than `last`, the domain is empty and no value can be parsed.
+    pub fn new(first: u32, last: u32) -> Self {
+        Self {
+            first,
+            last,
+            names: Vec::new(),
+        }
+    }
+
+    /// Create the domain of the days of week, from 0 (Sunday) to 6 (Saturday), with the English names and
+    /// abbreviations (e.g. `monday` and `mon`)
+    pub fn weekdays() -> Self {
+        Self::new(0, 6)
+            .names(WEEKDAY_NAMES)
+            .names(WEEKDAY_ABBREVIATIONS)
+    }
+
+    /// Create the domain of the months, from 1 (January) to 12 (December), with the English names and
+    /// abbreviations (e.g. `january` and `jan`)
+    pub fn months() -> Self {
+        Self::new(1, 12)
+            .names(MONTH_NAMES)
+            .names(MONTH_ABBREVIATIONS)
+    }
+
+    /// Create the domain of the hours of the day, from 0 to 23
+    pub fn hours() -> Self {
+        Self::new(0, 23)
+    }
+
+    /// Name the values of the domain in order, starting from the first one.
+    ///
+    /// Names are matched case-insensitively and can be added several times (e.g. full names and abbreviations).
+    /// Names beyond the end of the domain are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let shifts = range_parser::CyclicDomain::new(1, 3).names(["morning", "afternoon", "night"]);
+    /// assert_eq!(shifts.parse("NIGHT-afternoon").unwrap(), vec![3, 1, 2]);
+    /// ```
+    pub fn names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.names.extend(
+            names
+                .into_iter()
+                .zip(self.first..=self.last)
+                .map(|(name, value)| (name.as_ref().to_string(), value)),
+        );
+        self
+    }
+
+    /// Returns the values of the domain
+    pub fn domain(&self) -> RangeInclusive<u32> {
+        self.first..=self.last
+    }
+
+    /// Returns the value named `name`, which is matched case-insensitively
+    pub fn value(&self, name: &str) -> Option<u32> {
+        self.names
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    /// Parse a range string (e.g. `fri-mon,wed`) to a vector with all the values, in order.
+    ///
+    /// Ranges whose start is bigger than their end wrap around the end of the domain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let weekdays = range_parser::CyclicDomain::weekdays();
+    /// assert_eq!(weekdays.parse("sat-sun,3").unwrap(), vec![6, 0, 3]);
+    /// assert!(weekdays.parse("mon-funday").is_err());
+    /// ```
+    pub fn parse(&self, range_str: &str) -> RangeResult<Vec<u32>> {
+        // the grammar is the one of the default parser, with ranges wrapping around the domain
+        let parser = RangeParser::new();
+        let values =
+            parser.parse_parts(range_str, |segment| self.parse_segment(&parser, segment))?;
+
+        Ok(values.into_iter().flatten().collect())
+    }
+
+    /// Parse a segment (e.g. `mon`, `fri-mon` or `*:2`) to its values
+    fn parse_segment(&self, parser: &RangeParser, segment: &str) -> RangeResult<Vec<u32>> {
+        let (range, step) = parser.lex_stepped(segment)?;
+        let (start, end) = match range {
+            Syntax::Range(Some(start), Some(end)) => {
+                (self.parse_value(start)?, self.parse_value(end)?)
+            }
+            Syntax::Value(WILDCARD) if self.first <= self.last => (self.first, self.last),
+            Syntax::Value(value) if step.is_none() => {
+                return self.parse_value(value).map(|value| vec![value])
+            }
+            // a step is allowed only after a range, and ranges can't be open-ended
+            _ => return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(segment))),
+        };
+        let step = match step {
+            Some(step) => parse_step(step)?,
+            None => 1,
+        };
+
+        // values are offsets from the first one, so that the end can wrap around
+        let size = u64::from(self.last - self.first) + 1;
+        let start = u64::from(start - self.first);
+        let length = (u64::from(end - self.first) + size - start) % size;
+        Ok((0..=length)
+            .step_by(step)
+            .map(|offset| self.first + ((start + offset) % size) as u32)
+            .collect())
+    }
+
+    /// Parse a value or a name of the domain
+    fn parse_value(&self, text: &str) -> RangeResult<u32> {
+        let text = text.trim();
+        if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+            return self
+                .value(text)
+                .ok_or_else(|| RangeError::NotANumber(Fragment::unlocated(text)));
+        }
+
+        text.parse()
+            .ok()
+            .filter(|value| self.domain().contains(value))
+            .ok_or_else(|| RangeError::OutOfBounds(Fragment::unlocated(text)))
+    }
+}
+
+/// Parse the step of a range, which must be positive
+fn parse_step(step: &str) -> RangeResult<usize> {
+    match parse_as_t(step)? {
+        0 => Err(RangeError::ZeroStep(Fragment::unlocated(step))),
+        step => Ok(step),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_wrap_around_the_domain() {
+        let weekdays = CyclicDomain::weekdays();
+        assert_eq!(weekdays.parse("mon-fri").unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(weekdays.parse("fri-mon").unwrap(), vec![5, 6, 0, 1]);
+        assert_eq!(weekdays.parse("sun-sun").unwrap(), vec![0]);
+        assert_eq!(weekdays.parse("1-0").unwrap(), vec![1, 2, 3, 4, 5, 6, 0]);
+        assert_eq!(
+            CyclicDomain::months().parse("nov-feb").unwrap(),
+            vec![11, 12, 1, 2]
+        );
+    }
+
+    #[test]
+    fn should_step_around_the_domain() {
+        let hours = CyclicDomain::hours();
+        assert_eq!(hours.parse("20-3:3").unwrap(), vec![20, 23, 2]);
+        assert_eq!(hours.parse("*:6").unwrap(), vec![0, 6, 12, 18]);
+        assert_eq!(
+            CyclicDomain::weekdays().parse("fri-wed:2").unwrap(),
+            vec![5, 0, 2]
+        );
+    }
+
+    #[test]
+    fn should_match_names_case_insensitively() {
+        let weekdays = CyclicDomain::weekdays();
+        assert_eq!(
+            weekdays.parse("Saturday - MON, wed").unwrap(),
+            vec![6, 0, 1, 3]
+        );
+        assert_eq!(weekdays.value("FRIDAY"), Some(5));
+        assert_eq!(weekdays.value("someday"), None);
+        assert_eq!(CyclicDomain::months().value("Sep"), Some(9));
+    }
+
+    #[test]
+    fn should_ignore_names_beyond_the_domain() {
+        let domain = CyclicDomain::new(0, 1).names(["off", "on", "broken"]);
+        assert_eq!(domain.parse("on-off").unwrap(), vec![1, 0]);
+        assert!(domain.parse("broken").is_err());
+    }
+
+    #[test]
+    fn should_not_parse_invalid_cyclic_ranges() {
+        let weekdays = CyclicDomain::weekdays();
+        assert_eq!(
+            weekdays.parse("mon,funday-fri").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("funday", 4..10, 1))
+        );
+        assert_eq!(
+            weekdays.parse("0-7").unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("7", 2..3, 0))
+        );
+        assert_eq!(
+            weekdays.parse("mon-fri:0").unwrap_err(),
+            RangeError::ZeroStep(Fragment::new("0", 8..9, 0))
+        );
+        assert_eq!(
+            weekdays.parse("mon-fri-sat").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("mon-fri-sat", 0..11, 0))
+        );
+        assert_eq!(
+            weekdays.parse("sun,mon-").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("mon-", 4..8, 1))
+        );
+        assert!(weekdays.parse("mon:2").is_err());
+        assert!(weekdays.parse("").is_err());
+        assert!(weekdays.parse("mon-").is_err());
+        assert!(CyclicDomain::new(5, 1).parse("*").is_err());
+        assert!(CyclicDomain::new(5, 1).parse("3").is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::CronField;
+
+/// Parse error
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    #[error("Invalid range syntax: {0}")]
+    InvalidRangeSyntax(Fragment),
+    #[error("Not a number: {0}")]
+    NotANumber(Fragment),
+    #[error(
+        "Value, range, step, point count and factor separators and exclusion marker cannot be the same or contain each other"
+    )]
+    SeparatorsMustBeDifferent,
+    #[error("Start of the range cannot be bigger than the end: {0}")]
+    StartBiggerThanEnd(Fragment),
+    #[error("Ambiguous separator: {0}")]
+    AmbiguousSeparator(String),
+    #[error("Step of the range cannot be zero: {0}")]
+    ZeroStep(Fragment),
+    #[error("Step of the range must be positive: {0}")]
+    NegativeStep(Fragment),
+    #[error("Value out of bounds: {0}")]
+    OutOfBounds(Fragment),
+    #[error("Value cannot be zero: {0}")]
+    ZeroValue(Fragment),
+    #[error("Range cannot be divided evenly into the amount of points: {0}")]
+    UnevenPoints(Fragment),
+    #[error("Factor of the range must be greater than one: {0}")]
+    InvalidFactor(Fragment),
+    #[error("End of the range cannot be reached by multiplying the start: {0}")]
+    UnreachableEnd(Fragment),
+    #[error("Range too large: it would produce {0} values")]
+    TooLarge(u128),
+    #[error("Too many segments: {0}")]
+    TooManySegments(usize),
+    #[error("No range is satisfiable for a length of {0}")]
+    NotSatisfiable(u64),
+    #[error("Invalid {0} field: {1}")]
+    InvalidCronField(CronField, Box<RangeError>),
+    #[error("Value cannot be formatted into a range string: {0}")]
+    UnformattableValue(String),
+    #[error("Value and range separators of the format options cannot be empty, the same or contain each other")]
+    FormatSeparatorsMustBeDifferent,
+}
+
+/// Parse result
+pub type RangeResult<T> = Result<T, RangeError>;
+
+/// The fragment of the range string which caused an error, with its location in the input
+#[derive(Clone)]
+pub struct Fragment {
+    text: String,
+    span: Range<usize>,
+    segment: usize,
+    /// Address of the text when it's a slice of the range string, so that it's located without searching it
+    origin: Option<usize>,
+}
+
+impl fmt::Debug for Fragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fragment")
+            .field("text", &self.text)
+            .field("span", &self.span)
+            .field("segment", &self.segment)
+            .finish()
+    }
+}
+
+impl PartialEq for Fragment {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text && self.span == other.span && self.segment == other.segment
+    }
+}
+
+impl Eq for Fragment {}
+
+impl Fragment {
+    /// Create a new [`Fragment`]
+    ///
+    /// # Arguments
+    /// - text: the offending text
+    /// - span: the byte span of the text within the range string
+    /// - segment: the index of the segment, as delimited by the value separator, which contains the text
+    pub fn new(text: impl ToString, span: Range<usize>, segment: usize) -> Self {
+        Self {
+            text: text.to_string(),
+            span,
+            segment,
+            origin: None,
+        }
+    }
+
+    /// Create a [`Fragment`] which has not been located in the range string yet
+    ///
+    /// The text is usually a slice of the range string, as split by the lexer, so its address is kept to locate it.
+    pub(crate) fn unlocated(text: &str) -> Self {
+        Self {
+            origin: Some(text.as_ptr() as usize),
+            ..Self::new(text, 0..text.len(), 0)
+        }
+    }
+
+    /// Returns the offending text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the byte span of the text within the range string
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the index of the segment which contains the text
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+
+    /// Locate the fragment within `part`, which starts at `offset` in the range string and is the `segment`-th
+    /// segment.
+    ///
+    /// The text is located by its address if it's a slice of `part`, otherwise it's searched in `part` (e.g. it has
+    /// been rebuilt while parsing) and if it's not found the whole part is used.
+    fn locate(&mut self, part: &str, offset: usize, segment: usize) {
+        let text = self.text.trim();
+        let leading_whitespace = self.text.len() - self.text.trim_start().len();
+        let index = self
+            .origin
+            .and_then(|origin| origin.checked_sub(part.as_ptr() as usize))
+            .map(|index| index + leading_whitespace)
+            .filter(|index| {
+                part.get(*index..)
+                    .is_some_and(|rest| rest.starts_with(text))
+            })
+            .or_else(|| part.find(text));
+        self.span = match index.filter(|_| !text.is_empty()) {
+            Some(index) => offset + index..offset + index + text.len(),
+            None => offset..offset + part.len(),
+        };
+        self.segment = segment;
+    }
+}
+
+impl fmt::Display for Fragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl RangeError {
+    /// Returns the fragment of the range string which caused the error, if the error is related to the input
+    pub fn fragment(&self) -> Option<&Fragment> {
+        match self {
+            Self::InvalidRangeSyntax(fragment)
+            | Self::NotANumber(fragment)
+            | Self::StartBiggerThanEnd(fragment)
+            | Self::ZeroStep(fragment)
+            | Self::NegativeStep(fragment)
+            | Self::OutOfBounds(fragment)
+            | Self::ZeroValue(fragment)
+            | Self::UnevenPoints(fragment)
+            | Self::InvalidFactor(fragment)
+            | Self::UnreachableEnd(fragment) => Some(fragment),
+            Self::InvalidCronField(_, error) => error.fragment(),
+            Self::SeparatorsMustBeDifferent
+            | Self::AmbiguousSeparator(_)
+            | Self::TooLarge(_)
+            | Self::TooManySegments(_)
+            | Self::NotSatisfiable(_)
+            | Self::UnformattableValue(_)
+            | Self::FormatSeparatorsMustBeDifferent => None,
+        }
+    }
+
+    /// Returns the byte span within the range string of the text which caused the error
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.fragment().map(Fragment::span)
+    }
+
+    /// Returns the index of the segment, as delimited by the value separator, which caused the error
+    pub fn segment(&self) -> Option<usize> {
+        self.fragment().map(Fragment::segment)
+    }
+
+    /// Render the range string with a caret under the text which caused the error
+    ///
+    /// If the error is not related to the input, only the range string is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let input = "1,3-x,5";
+    /// let error = range_parser::parse::<u32>(input).unwrap_err();
+    /// assert_eq!(error.render(input), "1,3-x,5\n    ^");
+    /// ```
+    pub fn render(&self, input: &str) -> String {
+        let Some(span) = self.span() else {
+            return input.to_string();
+        };
+        let start = input.get(..span.start).unwrap_or(input).chars().count();
+        let width = input
+            .get(span)
+            .map(|text| text.chars().count())
+            .unwrap_or_default()
+            .max(1);
+
+        format!("{input}\n{}{}", " ".repeat(start), "^".repeat(width))
+    }
+
+    /// Set the location of the error within `part`, which starts at `offset` in the range string and is the
+    /// `segment`-th segment
+    pub(crate) fn locate(mut self, part: &str, offset: usize, segment: usize) -> Self {
+        if let Some(fragment) = self.fragment_mut() {
+            fragment.locate(part, offset, segment);
+        }
+        self
+    }
+
+    /// Replace the text of the fragment, e.g. with the original text when the error comes from a rebuilt string
+    pub(crate) fn with_text(mut self, text: &str) -> Self {
+        if let Some(fragment) = self.fragment_mut() {
+            fragment.text = text.to_string();
+        }
+        self
+    }
+
+    fn fragment_mut(&mut self) -> Option<&mut Fragment> {
+        match self {
+            Self::InvalidRangeSyntax(fragment)
+            | Self::NotANumber(fragment)
+            | Self::StartBiggerThanEnd(fragment)
+            | Self::ZeroStep(fragment)
+            | Self::NegativeStep(fragment)
+            | Self::OutOfBounds(fragment)
+            | Self::ZeroValue(fragment)
+            | Self::UnevenPoints(fragment)
+            | Self::InvalidFactor(fragment)
+            | Self::UnreachableEnd(fragment) => Some(fragment),
+            Self::InvalidCronField(_, error) => error.fragment_mut(),
+            Self::SeparatorsMustBeDifferent
+            | Self::AmbiguousSeparator(_)
+            | Self::TooLarge(_)
+            | Self::TooManySegments(_)
+            | Self::NotSatisfiable(_)
+            | Self::UnformattableValue(_)
+            | Self::FormatSeparatorsMustBeDifferent => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_locate_fragment_in_part() {
+        let error = RangeError::NotANumber(Fragment::unlocated("x")).locate("3-x", 2, 1);
+        assert_eq!(error, RangeError::NotANumber(Fragment::new("x", 4..5, 1)));
+        assert_eq!(error.span(), Some(4..5));
+        assert_eq!(error.segment(), Some(1));
+    }
+
+    #[test]
+    fn should_locate_slice_of_part_by_its_address() {
+        let part = "1-3:1-";
+        let error = RangeError::NotANumber(Fragment::unlocated(&part[4..])).locate(part, 2, 1);
+        assert_eq!(error, RangeError::NotANumber(Fragment::new("1-", 6..8, 1)));
+        let error = RangeError::NotANumber(Fragment::unlocated(" 1-")).locate(part, 2, 1);
+        assert_eq!(error.span(), Some(2..4));
+    }
+
+    #[test]
+    fn should_locate_whole_part_if_text_is_not_found() {
+        let error = RangeError::NotANumber(Fragment::unlocated("-z")).locate("-x-y", 4, 2);
+        assert_eq!(error.span(), Some(4..8));
+        assert_eq!(error.segment(), Some(2));
+    }
+
+    #[test]
+    fn should_not_locate_errors_unrelated_to_input() {
+        let error = RangeError::SeparatorsMustBeDifferent.locate("1", 0, 0);
+        assert_eq!(error.span(), None);
+        assert_eq!(error.segment(), None);
+        assert_eq!(error.render("1,2"), "1,2");
+    }
+
+    #[test]
+    fn should_render_caret_under_error() {
+        let error = RangeError::StartBiggerThanEnd(Fragment::new("5-1", 2..5, 1));
+        assert_eq!(error.render("0,5-1"), "0,5-1\n  ^^^");
+    }
+
+    #[test]
+    fn should_render_caret_with_multibyte_chars() {
+        let error = RangeError::NotANumber(Fragment::new("x", 5..6, 1));
+        assert_eq!(error.render("1€,x"), "1€,x\n   ^");
+    }
+
+    #[test]
+    fn should_display_fragment_text() {
+        let error = RangeError::NotANumber(Fragment::new("x", 4..5, 1));
+        assert_eq!(error.to_string(), "Not a number: x");
+    }
+}
@@ -0,0 +1,325 @@
+use std::fmt::Display;
+
+use crate::lexer::Syntax;
+use crate::parser::AMBIGOUS_RANGE_SEPARATORS;
+use crate::{RangeError, RangeParser, RangeResult, Unit};
+
+/// Options to format values into a range string
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::FormatOptions;
+///
+/// let options = FormatOptions::new()
+///     .value_separator(";")
+///     .range_separator("..")
+///     .sort(true)
+///     .min_run_length(3);
+/// assert_eq!(range_parser::format_with_options(&[5, 1, 2, 8, 7, 6], &options).unwrap(), "1;2;5..8");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    value_separator: String,
+    range_separator: String,
+    sort: bool,
+    min_run_length: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            value_separator: ",".to_string(),
+            range_separator: "-".to_string(),
+            sort: false,
+            min_run_length: 2,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Create the default format options: `,` as value separator, `-` as range separator,
+    /// input order is kept and runs of at least 2 values are collapsed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the separator between single values and ranges
+    pub fn value_separator(mut self, value_separator: impl ToString) -> Self {
+        self.value_separator = value_separator.to_string();
+        self
+    }
+
+    /// Set the separator between the start and the end of a range
+    pub fn range_separator(mut self, range_separator: impl ToString) -> Self {
+        self.range_separator = range_separator.to_string();
+        self
+    }
+
+    /// Set whether values must be sorted before being formatted. Otherwise the input order is kept.
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Set the minimum amount of consecutive values required to collapse them into a range.
+    ///
+    /// Shorter runs are formatted as single values. Values lower than 2 are treated as 2.
+    pub fn min_run_length(mut self, min_run_length: usize) -> Self {
+        self.min_run_length = min_run_length;
+        self
+    }
+
+    /// Check that the separators can divide the formatted values, so that they're parsed back
+    fn validate(&self) -> RangeResult<()> {
+        let (value_separator, range_separator) = (&self.value_separator, &self.range_separator);
+        if value_separator.is_empty()
+            || range_separator.is_empty()
+            || value_separator.contains(range_separator.as_str())
+            || range_separator.contains(value_separator.as_str())
+        {
+            return Err(RangeError::FormatSeparatorsMustBeDifferent);
+        }
+        if AMBIGOUS_RANGE_SEPARATORS.contains(&range_separator.as_str()) {
+            return Err(RangeError::AmbiguousSeparator(range_separator.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Format values into the shortest range string, using `,` as value separator and `-` as range separator
+///
+/// Consecutive values (i.e. values which differ by one unit) are collapsed into ranges, keeping the input order,
+/// so that parsing the result with [`crate::parse`] returns the same values.
+///
+/// Fails with [`RangeError::UnformattableValue`] if a value couldn't be parsed back, because it contains a
+/// separator (e.g. the char `-`).
+///
+/// # Example
+///
+/// ```rust
+/// let range_str = range_parser::format(&[1, 3, 4, 5, 2]).unwrap();
+/// assert_eq!(range_str, "1,3-5,2");
+/// assert_eq!(range_parser::parse::<i32>(&range_str).unwrap(), vec![1, 3, 4, 5, 2]);
+///
+/// assert!(range_parser::format(&['+', ',', '-']).is_err());
+/// ```
+pub fn format<T>(values: &[T]) -> RangeResult<String>
+where
+    T: Display + PartialEq + PartialOrd + Unit + Clone,
+{
+    format_with_options(values, &FormatOptions::default())
+}
+
+/// Format values into the shortest range string with custom separators
+///
+/// # Arguments
+/// - values: &[T] - the values to format
+/// - value_separator: &str - the separator for single values
+/// - range_separator: &str - the separator for ranges
+///
+/// # Returns
+/// - Result<String, RangeError> - the formatted range, which can be parsed back with [`crate::parse_with`]
+///
+/// # Example
+///
+/// ```rust
+/// let range_str = range_parser::format_with(&[-2, 0, 1, 2, 3, -1, 7], ";", "..").unwrap();
+/// assert_eq!(range_str, "-2;0..3;-1;7");
+/// ```
+pub fn format_with<T>(
+    values: &[T],
+    value_separator: &str,
+    range_separator: &str,
+) -> RangeResult<String>
+where
+    T: Display + PartialEq + PartialOrd + Unit + Clone,
+{
+    format_with_options(
+        values,
+        &FormatOptions::default()
+            .value_separator(value_separator)
+            .range_separator(range_separator),
+    )
+}
+
+/// Format values into a range string with the provided [`FormatOptions`]
+///
+/// Fails with [`RangeError::FormatSeparatorsMustBeDifferent`] if the separators are empty or contain each other,
+/// with [`RangeError::AmbiguousSeparator`] if the range separator is ambiguous (e.g. `--`) and with
+/// [`RangeError::UnformattableValue`] if a value couldn't be parsed back with the separators of the options.
+pub fn format_with_options<T>(values: &[T], options: &FormatOptions) -> RangeResult<String>
+where
+    T: Display + PartialEq + PartialOrd + Unit + Clone,
+{
+    options.validate()?;
+    // formatted values must be parsed back by the parser of the `*_with` functions
+    let parser = RangeParser::with_separators(&options.value_separator, &options.range_separator);
+    let mut values = values.to_vec();
+    if options.sort {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    }
+    let min_run_length = options.min_run_length.max(2);
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < values.len() {
+        // find the last value of the run of consecutive values
+        let mut end = start;
+        while end + 1 < values.len()
+            && values[end].forward_checked(1).as_ref() == Some(&values[end + 1])
+        {
+            end += 1;
+        }
+
+        let run = &values[start..=end];
+        if run.len() >= min_run_length {
+            let (first, last) = (values[start].to_string(), values[end].to_string());
+            let range = format!("{first}{}{last}", options.range_separator);
+            if !parser.lexes_as(&range, Syntax::Range(Some(&first), Some(&last))) {
+                return Err(RangeError::UnformattableValue(range));
+            }
+            parts.push(range);
+        } else {
+            for value in run.iter().map(|value| value.to_string()) {
+                if !parser.lexes_as(&value, Syntax::Value(&value)) {
+                    return Err(RangeError::UnformattableValue(value));
+                }
+                parts.push(value);
+            }
+        }
+        start = end + 1;
+    }
+
+    Ok(parts.join(&options.value_separator))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{parse, parse_with};
+
+    #[test]
+    fn should_format_values_keeping_order() {
+        assert_eq!(format(&[1u64, 3, 4, 5, 2]).unwrap(), "1,3-5,2");
+        assert_eq!(format(&[5u64, 4, 3]).unwrap(), "5,4,3");
+        assert_eq!(format::<u64>(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn should_format_negative_values() {
+        assert_eq!(
+            format(&[-8, -5, -4, -3, -2, -1, 0, 1, 2, 3, -1]).unwrap(),
+            "-8,-5-3,-1"
+        );
+    }
+
+    #[test]
+    fn should_format_with_custom_separators() {
+        assert_eq!(
+            format_with(&[-2, 0, 1, 2, 3, -1, 7], ";", "..").unwrap(),
+            "-2;0..3;-1;7"
+        );
+    }
+
+    #[test]
+    fn should_format_sorted_values() {
+        let options = FormatOptions::new().sort(true);
+        assert_eq!(
+            format_with_options(&[8, 3, 1, 2, 7, 9], &options).unwrap(),
+            "1-3,7-9"
+        );
+    }
+
+    #[test]
+    fn should_format_with_min_run_length() {
+        let options = FormatOptions::new().min_run_length(3);
+        assert_eq!(
+            format_with_options(&[1, 2, 4, 5, 6], &options).unwrap(),
+            "1,2,4-6"
+        );
+        let options = FormatOptions::new().min_run_length(0);
+        assert_eq!(format_with_options(&[1, 2, 4], &options).unwrap(), "1-2,4");
+    }
+
+    #[test]
+    fn should_format_duplicates() {
+        assert_eq!(format(&[1, 1, 2, 3]).unwrap(), "1,1-3");
+    }
+
+    #[test]
+    fn should_format_values_at_type_bounds() {
+        assert_eq!(format(&[0u8, 1, 2, 254, 255]).unwrap(), "0-2,254-255");
+    }
+
+    #[test]
+    fn should_format_wrappers() {
+        use std::num::{Saturating, Wrapping};
+
+        assert_eq!(
+            format(&[Wrapping(1u8), Wrapping(2), Wrapping(255)]).unwrap(),
+            "1-2,255"
+        );
+        assert_eq!(format(&[Saturating(-1i8), Saturating(0)]).unwrap(), "-1-0");
+    }
+
+    #[test]
+    fn should_not_format_values_containing_separators() {
+        let values: Vec<char> = ('+'..='-').collect();
+        assert_eq!(
+            format(&values).unwrap_err(),
+            RangeError::UnformattableValue("+--".to_string())
+        );
+        assert_eq!(
+            format(&['-', 'a']).unwrap_err(),
+            RangeError::UnformattableValue("-".to_string())
+        );
+        assert_eq!(
+            format(&[',']).unwrap_err(),
+            RangeError::UnformattableValue(",".to_string())
+        );
+        assert!(format(&[':']).is_err());
+        assert!(format(&['!']).is_err());
+        // with other separators, the chars are parsed back
+        let range_str = format_with(&values, ";", "..").unwrap();
+        assert_eq!(range_str, "+..-");
+        assert_eq!(parse_with::<char>(&range_str, ";", "..").unwrap(), values);
+        assert_eq!(format(&['a', 'b', 'c', 'x']).unwrap(), "a-c,x");
+    }
+
+    #[test]
+    fn should_validate_format_separators() {
+        for (value_separator, range_separator) in
+            [(",", ","), (",", ",,"), ("..", "."), ("", "-"), (",", "")]
+        {
+            assert_eq!(
+                format_with(&[1, 2, 3], value_separator, range_separator).unwrap_err(),
+                RangeError::FormatSeparatorsMustBeDifferent
+            );
+        }
+        assert_eq!(
+            format_with::<i32>(&[], ",", "--").unwrap_err(),
+            RangeError::AmbiguousSeparator("--".to_string())
+        );
+        assert_eq!(
+            RangeError::FormatSeparatorsMustBeDifferent.to_string(),
+            "Value and range separators of the format options cannot be empty, the same or contain each other"
+        );
+    }
+
+    #[test]
+    fn should_round_trip() {
+        for range_str in ["1,3-5,2", "-8,-5-3,-1", "0-255,7", "10,9,8"] {
+            let values: Vec<i32> = parse(range_str).unwrap();
+            assert_eq!(parse::<i32>(&format(&values).unwrap()).unwrap(), values);
+        }
+        let values: Vec<i32> = parse_with("-2;0..3;-1;7", ";", "..").unwrap();
+        assert_eq!(
+            parse_with::<i32>(&format_with(&values, ";", "..").unwrap(), ";", "..").unwrap(),
+            values
+        );
+    }
+}
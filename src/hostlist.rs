@@ -0,0 +1,508 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{Fragment, RangeError, RangeParser, RangeResult, RangeSet};
+
+const HOST_SEPARATOR: char = ',';
+const GROUP_START: char = '[';
+const GROUP_END: char = ']';
+
+/// Expand a Slurm/ClusterShell style hostlist (e.g. `node[01-10,15]`) into the list of hostnames
+///
+/// The hostlist is a comma-separated list of expressions, where each group between brackets is a comma-separated
+/// list of non-negative numbers, ranges and ranges with a step (e.g. `[1,3-5,10-20:5]`). The other syntaxes of
+/// [`crate::parse`], such as exclusions, point counts and factors, are not allowed within groups.
+///
+/// - numbers keep the zero padding of their range, taken from the padded endpoint (e.g. `[08-10]` is `08,09,10`
+///   and `[8-010]` is `008,009,010`)
+/// - expressions with several groups expand to every combination of their values, where the first group changes
+///   the slowest (e.g. `rack[1-2]-n[01-02]` is `rack1-n01,rack1-n02,rack2-n01,rack2-n02`)
+///
+/// # Arguments
+/// - hostlist: &str - the hostlist
+///
+/// # Returns
+/// - Result<Vec<String>, RangeError> - the hostnames, in order.
+///
+/// Errors are located in the hostlist and their segment is the index of the expression.
+///
+/// # Example
+///
+/// ```rust
+/// let hosts = range_parser::expand_hostlist("gpu-node[001-003],login[1,3]").unwrap();
+/// assert_eq!(
+///     hosts,
+///     vec!["gpu-node001", "gpu-node002", "gpu-node003", "login1", "login3"]
+/// );
+/// ```
+pub fn expand_hostlist(hostlist: &str) -> RangeResult<Vec<String>> {
+    let parser = RangeParser::new()
+        .point_count_separator("")
+        .factor_separator("")
+        .exclusion_marker("");
+    let mut hosts = Vec::new();
+    for (index, (offset, expression)) in split_expressions(hostlist)?.into_iter().enumerate() {
+        hosts.extend(expand_expression(&parser, expression, offset, index)?);
+    }
+
+    Ok(hosts)
+}
+
+/// Compress a list of hostnames into the shortest hostlist expression (e.g. `node[01-10,15]`), which is the
+/// inverse of [`expand_hostlist`].
+///
+/// Hostnames which differ only by a number are folded into a bracket group, starting from the last number, so that
+/// hostnames with several numbers are folded into several groups (e.g. `rack[1-2]-n[01-04]`). Numbers are folded
+/// only with numbers of the same zero padding.
+///
+/// Duplicated hostnames are ignored, groups are sorted and expressions are in the order of their first hostname.
+///
+/// # Example
+///
+/// ```rust
+/// let hosts = ["rack1-n01", "rack1-n02", "rack2-n01", "rack2-n02", "login"];
+/// assert_eq!(range_parser::compress_hostlist(hosts), "rack[1-2]-n[01-02],login");
+///
+/// let hosts = ["node15", "node01", "node03", "node02"];
+/// assert_eq!(range_parser::compress_hostlist(hosts), "node[01-03,15]");
+/// ```
+pub fn compress_hostlist<I, S>(hosts: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut known = HashSet::new();
+    let mut hosts: Vec<Host> = hosts
+        .into_iter()
+        .filter(|host| known.insert(host.as_ref().to_string()))
+        .map(|host| Host::parse(host.as_ref()))
+        .collect();
+    let dimensions = hosts
+        .iter()
+        .map(|host| host.fields.len())
+        .max()
+        .unwrap_or_default();
+    // the last numbers are folded first, so that the previous ones can be folded over the groups
+    for from_end in 0..dimensions {
+        hosts = fold(hosts, from_end);
+    }
+
+    hosts
+        .iter()
+        .map(Host::to_string)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Split the hostlist into its expressions, with their offset, ignoring the commas within brackets
+fn split_expressions(hostlist: &str) -> RangeResult<Vec<(usize, &str)>> {
+    let mut expressions = Vec::new();
+    let mut start = 0;
+    let mut group_start = None;
+    for (index, c) in hostlist.char_indices() {
+        match c {
+            // groups can't be nested
+            GROUP_START if group_start.is_some() => {
+                return Err(RangeError::InvalidRangeSyntax(Fragment::new(
+                    c,
+                    index..index + 1,
+                    expressions.len(),
+                )));
+            }
+            GROUP_START => group_start = Some(index),
+            GROUP_END if group_start.is_none() => {
+                return Err(RangeError::InvalidRangeSyntax(Fragment::new(
+                    c,
+                    index..index + 1,
+                    expressions.len(),
+                )));
+            }
+            GROUP_END => group_start = None,
+            HOST_SEPARATOR if group_start.is_none() => {
+                expressions.push((start, &hostlist[start..index]));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if let Some(group_start) = group_start {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::new(
+            &hostlist[group_start..],
+            group_start..hostlist.len(),
+            expressions.len(),
+        )));
+    }
+    expressions.push((start, &hostlist[start..]));
+
+    // empty expressions are ignored (e.g. `a,,b`)
+    Ok(expressions
+        .into_iter()
+        .filter(|(_, expression)| !expression.trim().is_empty())
+        .map(|(offset, expression)| {
+            let trimmed = expression.trim_start();
+            (
+                offset + expression.len() - trimmed.len(),
+                trimmed.trim_end(),
+            )
+        })
+        .collect())
+}
+
+/// Expand an expression (e.g. `rack[1-2]-n[01-04]`), which starts at `offset` in the hostlist and is the
+/// `index`-th expression
+fn expand_expression(
+    parser: &RangeParser,
+    expression: &str,
+    offset: usize,
+    index: usize,
+) -> RangeResult<Vec<String>> {
+    let mut hosts = vec![String::new()];
+    let mut rest = expression;
+    while let Some((literal, group)) = rest.split_once(GROUP_START) {
+        // brackets are balanced, as checked while splitting the expressions
+        let (group, after) = group.split_once(GROUP_END).unwrap_or((group, ""));
+        let group_offset = offset + (expression.len() - rest.len()) + literal.len() + 1;
+        let values = expand_group(parser, group, group_offset, index)?;
+        hosts = hosts
+            .into_iter()
+            .flat_map(|host| {
+                values
+                    .iter()
+                    .map(move |value| format!("{host}{literal}{value}"))
+            })
+            .collect();
+        rest = after;
+    }
+
+    Ok(hosts
+        .into_iter()
+        .map(|host| format!("{host}{rest}"))
+        .collect())
+}
+
+/// Expand the range string of a group (e.g. `01-10,15`) into the zero-padded values
+fn expand_group(
+    parser: &RangeParser,
+    group: &str,
+    mut offset: usize,
+    index: usize,
+) -> RangeResult<Vec<String>> {
+    let mut values = Vec::new();
+    for segment in group.split(HOST_SEPARATOR) {
+        let range: Vec<u64> = parser
+            .parse(segment)
+            .map_err(|err| err.locate(segment, offset, index))?;
+        // the endpoints are the first numbers of the segment, before the step
+        let width = segment
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .take(2)
+            .map(padding)
+            .max()
+            .unwrap_or_default();
+        values.extend(range.into_iter().map(|value| format!("{value:0width$}")));
+        offset += segment.len() + 1;
+    }
+
+    Ok(values)
+}
+
+/// Returns the width of a number, if it is zero-padded (e.g. `007`), otherwise 0
+fn padding(number: &str) -> usize {
+    if number.len() > 1 && number.starts_with('0') {
+        number.len()
+    } else {
+        0
+    }
+}
+
+/// A hostname split into its numbers and the text around them
+#[derive(Debug, Clone)]
+struct Host {
+    /// The text before, between and after the numbers
+    literals: Vec<String>,
+    fields: Vec<Field>,
+}
+
+/// The text and the numbers of a hostname, except for the number being folded
+type HostKey = (Vec<String>, Vec<String>);
+
+/// A number of a hostname, which holds several values once folded
+#[derive(Debug, Clone)]
+struct Field {
+    values: Vec<u64>,
+    width: usize,
+}
+
+impl Host {
+    /// Split `host` into its numbers and the text around them
+    fn parse(host: &str) -> Self {
+        let mut literals = Vec::new();
+        let mut fields = Vec::new();
+        let mut literal = String::new();
+        let mut rest = host;
+        while let Some(number_start) = rest.find(|c: char| c.is_ascii_digit()) {
+            let (before, number) = rest.split_at(number_start);
+            let number_end = number
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(number.len());
+            let (number, after) = number.split_at(number_end);
+            literal.push_str(before);
+            match number.parse() {
+                Ok(value) => {
+                    literals.push(std::mem::take(&mut literal));
+                    fields.push(Field {
+                        values: vec![value],
+                        width: padding(number),
+                    });
+                }
+                // numbers which don't fit in a u64 are kept as text
+                Err(_) => literal.push_str(number),
+            }
+            rest = after;
+        }
+        literal.push_str(rest);
+        literals.push(literal);
+
+        Self { literals, fields }
+    }
+
+    /// Returns the key of the hosts which can be folded with this one over the `dimension`-th number
+    fn key(&self, dimension: usize) -> HostKey {
+        let other_fields = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != dimension)
+            .map(|(_, field)| field.to_string())
+            .collect();
+
+        (self.literals.clone(), other_fields)
+    }
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (literal, field) in self.literals.iter().zip(&self.fields) {
+            write!(f, "{literal}{field}")?;
+        }
+        write!(f, "{}", self.literals.last().map_or("", String::as_str))
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width;
+        if let [value] = self.values.as_slice() {
+            return write!(f, "{value:0width$}");
+        }
+
+        let set: RangeSet<u64> = self.values.iter().copied().collect();
+        write!(f, "{GROUP_START}")?;
+        for (index, interval) in set.intervals().iter().enumerate() {
+            if index > 0 {
+                write!(f, "{HOST_SEPARATOR}")?;
+            }
+            let (start, end) = (interval.start(), interval.end());
+            if start == end {
+                write!(f, "{start:0width$}")?;
+            } else {
+                write!(f, "{start:0width$}-{end:0width$}")?;
+            }
+        }
+        write!(f, "{GROUP_END}")
+    }
+}
+
+/// Fold the hosts which differ only by their `from_end`-th number, counting from the last one
+fn fold(hosts: Vec<Host>, from_end: usize) -> Vec<Host> {
+    // hosts are folded together if everything but the number is the same
+    let hosts: Vec<(Host, Option<(usize, HostKey)>)> = hosts
+        .into_iter()
+        .map(|host| {
+            let key = host
+                .fields
+                .len()
+                .checked_sub(from_end + 1)
+                .map(|dimension| (dimension, host.key(dimension)));
+            (host, key)
+        })
+        .collect();
+    let mut widths: HashMap<&HostKey, Vec<usize>> = HashMap::new();
+    for (host, key) in &hosts {
+        if let Some((dimension, key)) = key {
+            widths
+                .entry(key)
+                .or_default()
+                .push(host.fields[*dimension].width);
+        }
+    }
+
+    let mut folded: Vec<Host> = Vec::with_capacity(hosts.len());
+    let mut groups: HashMap<(&HostKey, usize), usize> = HashMap::new();
+    for (host, key) in &hosts {
+        let Some((dimension, key)) = key else {
+            folded.push(host.clone());
+            continue;
+        };
+        let mut host = host.clone();
+        let field = &mut host.fields[*dimension];
+        // a number which isn't padded can join a padded group as wide as its digits (e.g. `15` in `[01-15]`)
+        if field.width == 0 {
+            let digits = field.to_string().len();
+            field.width = widths[key]
+                .iter()
+                .copied()
+                .filter(|width| *width <= digits)
+                .max()
+                .unwrap_or_default();
+        }
+        match groups.get(&(key, field.width)) {
+            Some(&group) => folded[group].fields[*dimension]
+                .values
+                .extend_from_slice(&field.values),
+            None => {
+                groups.insert((key, field.width), folded.len());
+                folded.push(host);
+            }
+        }
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_expand_hostlist() {
+        assert_eq!(
+            expand_hostlist("node[01-03,15]").unwrap(),
+            vec!["node01", "node02", "node03", "node15"]
+        );
+        assert_eq!(
+            expand_hostlist("login, node[8-10]").unwrap(),
+            vec!["login", "node8", "node9", "node10"]
+        );
+        assert_eq!(
+            expand_hostlist("n[08-10],n[1-5:2]").unwrap(),
+            vec!["n08", "n09", "n10", "n1", "n3", "n5"]
+        );
+        assert!(expand_hostlist("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_pad_numbers_as_the_padded_endpoint() {
+        assert_eq!(
+            expand_hostlist("n[8-010]").unwrap(),
+            vec!["n008", "n009", "n010"]
+        );
+        assert_eq!(
+            expand_hostlist("n[09-100:30]").unwrap(),
+            vec!["n09", "n39", "n69", "n99"]
+        );
+        assert_eq!(expand_hostlist("n[1-3:02]").unwrap(), vec!["n1", "n3"]);
+    }
+
+    #[test]
+    fn should_allow_only_values_ranges_and_steps_in_groups() {
+        assert_eq!(
+            expand_hostlist("n[1-10:3]").unwrap(),
+            vec!["n1", "n4", "n7", "n10"]
+        );
+        assert_eq!(
+            expand_hostlist("n[1-5,!3]").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("!3", 6..8, 0))
+        );
+        assert!(matches!(
+            expand_hostlist("n[1-5#3]").unwrap_err(),
+            RangeError::NotANumber(_)
+        ));
+        assert!(matches!(
+            expand_hostlist("n[1-8*2]").unwrap_err(),
+            RangeError::NotANumber(_)
+        ));
+        assert!(expand_hostlist("n[*]").is_err());
+        assert!(expand_hostlist("n[1..3]").is_err());
+    }
+
+    #[test]
+    fn should_expand_cartesian_product_of_groups() {
+        assert_eq!(
+            expand_hostlist("rack[1-2]-n[01-02].local").unwrap(),
+            vec![
+                "rack1-n01.local",
+                "rack1-n02.local",
+                "rack2-n01.local",
+                "rack2-n02.local"
+            ]
+        );
+        assert_eq!(
+            expand_hostlist("[a]").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("a", 1..2, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_expand_invalid_hostlist() {
+        assert_eq!(
+            expand_hostlist("a,node[1-x]").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 9..10, 1))
+        );
+        assert_eq!(
+            expand_hostlist("node[3-1]").unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("3-1", 5..8, 0))
+        );
+        assert_eq!(
+            expand_hostlist("a,node[1-2").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("[1-2", 6..10, 1))
+        );
+        assert!(expand_hostlist("node]").is_err());
+        assert!(expand_hostlist("node[[1]]").is_err());
+        assert!(expand_hostlist("node[]").is_err());
+        assert!(expand_hostlist("node[!1]").is_err());
+        assert!(expand_hostlist("node[-1]").is_err());
+    }
+
+    #[test]
+    fn should_compress_hostlist() {
+        assert_eq!(
+            compress_hostlist(["node1", "node2", "node3", "node5", "node2"]),
+            "node[1-3,5]"
+        );
+        assert_eq!(
+            compress_hostlist(["gpu-node009", "gpu-node010", "gpu-node011"]),
+            "gpu-node[009-011]"
+        );
+        assert_eq!(compress_hostlist(["login", "node7"]), "login,node7");
+        assert_eq!(compress_hostlist(Vec::<String>::new()), "");
+    }
+
+    #[test]
+    fn should_compress_several_numbers() {
+        let hosts = expand_hostlist("rack[1-3]-n[01-04]").unwrap();
+        assert_eq!(compress_hostlist(&hosts), "rack[1-3]-n[01-04]");
+        assert_eq!(compress_hostlist(["r1n1", "r1n2", "r2n1"]), "r1n[1-2],r2n1");
+    }
+
+    #[test]
+    fn should_not_fold_numbers_with_different_padding() {
+        let hosts = ["n1", "n01", "n2", "n02"];
+        let hostlist = compress_hostlist(hosts);
+        assert_eq!(hostlist, "n[1-2],n[01-02]");
+        assert_eq!(compress_hostlist(["n15", "n01", "n9"]), "n[01,15],n9");
+        assert_eq!(
+            expand_hostlist(&hostlist).unwrap(),
+            vec!["n1", "n2", "n01", "n02"]
+        );
+    }
+
+    #[test]
+    fn should_keep_huge_numbers_as_text() {
+        let hosts = ["n99999999999999999999999", "n1", "n2"];
+        assert_eq!(compress_hostlist(hosts), "n99999999999999999999999,n[1-2]");
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::{Fragment, RangeError, RangeResult};
+
+/// The range unit for byte ranges
+const BYTES_UNIT: &str = "bytes";
+
+/// A range of an HTTP `Range` header, as defined by RFC 9110 (section 14.1.1)
+///
+/// Positions are zero-based and the last position is included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRange {
+    /// From the first position to the last position (e.g. `0-499`)
+    Int(u64, u64),
+    /// From the first position to the end of the representation (e.g. `9500-`)
+    From(u64),
+    /// The last bytes of the representation, by length (e.g. `-500`)
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Returns the positions of the range within a representation of `length` bytes, or `None` if the range is
+    /// not satisfiable.
+    ///
+    /// Ranges going beyond the end of the representation are shortened to its end.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::ByteRange;
+    ///
+    /// assert_eq!(ByteRange::Int(0, 499).resolve(300), Some(0..=299));
+    /// assert_eq!(ByteRange::Suffix(500).resolve(10000), Some(9500..=9999));
+    /// assert_eq!(ByteRange::From(9500).resolve(9000), None);
+    /// ```
+    pub fn resolve(&self, length: u64) -> Option<RangeInclusive<u64>> {
+        let last = length.checked_sub(1)?;
+        match *self {
+            Self::Int(first, end) if first <= last => Some(first..=end.min(last)),
+            Self::From(first) if first <= last => Some(first..=last),
+            Self::Suffix(suffix) if suffix > 0 => Some(length.saturating_sub(suffix)..=last),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(first, last) => write!(f, "{first}-{last}"),
+            Self::From(first) => write!(f, "{first}-"),
+            Self::Suffix(suffix) => write!(f, "-{suffix}"),
+        }
+    }
+}
+
+/// The value of an HTTP `Range` header (e.g. `bytes=0-499,-500`), as defined by RFC 9110 (section 14.2)
+///
+/// Contrary to [`crate::parse`], `-500` is a suffix range (the last 500 bytes), not a negative number.
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::{ByteRange, RangeHeader};
+///
+/// let header: RangeHeader = "bytes=0-499, 9500-, -500".parse().unwrap();
+/// assert_eq!(header.unit(), "bytes");
+/// assert_eq!(
+///     header.ranges(),
+///     &[ByteRange::Int(0, 499), ByteRange::From(9500), ByteRange::Suffix(500)]
+/// );
+/// assert_eq!(header.resolve(10000).unwrap(), vec![0..=499, 9500..=9999, 9500..=9999]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeHeader {
+    unit: String,
+    ranges: Vec<ByteRange>,
+}
+
+impl RangeHeader {
+    /// Returns the range unit (e.g. `bytes`)
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// Returns the ranges in the header order
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+
+    /// Returns whether the range unit is `bytes`, which is compared case-insensitively
+    pub fn is_bytes(&self) -> bool {
+        self.unit.eq_ignore_ascii_case(BYTES_UNIT)
+    }
+
+    /// Resolve the ranges against a representation of `length` bytes, in the header order.
+    ///
+    /// Unsatisfiable ranges are ignored, as long as one of them is satisfiable, otherwise
+    /// [`RangeError::NotSatisfiable`] is returned.
+    pub fn resolve(&self, length: u64) -> RangeResult<Vec<RangeInclusive<u64>>> {
+        let ranges: Vec<RangeInclusive<u64>> = self
+            .ranges
+            .iter()
+            .filter_map(|range| range.resolve(length))
+            .collect();
+        if ranges.is_empty() {
+            return Err(RangeError::NotSatisfiable(length));
+        }
+
+        Ok(ranges)
+    }
+}
+
+impl fmt::Display for RangeHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}=", self.unit)?;
+        for (index, range) in self.ranges.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{range}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RangeHeader {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((raw_unit, range_set)) = s.split_once('=') else {
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(s)));
+        };
+        let unit = raw_unit.trim();
+        if unit.is_empty() || !unit.chars().all(is_token_char) {
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(unit)).locate(s, 0, 0));
+        }
+
+        let range_set_offset = raw_unit.len() + 1;
+        let mut offset = range_set_offset;
+        let mut ranges = Vec::new();
+        for (index, part) in range_set.split(',').enumerate() {
+            // empty list elements are ignored (e.g. `0-1,,2-3`)
+            if !part.trim().is_empty() {
+                ranges.push(parse_byte_range(part).map_err(|err| err.locate(part, offset, index))?);
+            }
+            offset += part.len() + 1;
+        }
+        if ranges.is_empty() {
+            return Err(
+                RangeError::InvalidRangeSyntax(Fragment::unlocated(range_set)).locate(
+                    range_set,
+                    range_set_offset,
+                    0,
+                ),
+            );
+        }
+
+        Ok(Self {
+            unit: unit.to_string(),
+            ranges,
+        })
+    }
+}
+
+/// Parse the value of an HTTP `Range` header and resolve it against a representation of `length` bytes
+///
+/// Suffix ranges (e.g. `-500`) take the last bytes of the representation, open-ended ranges (e.g. `9500-`) go to
+/// its end and ranges going beyond its end are shortened.
+///
+/// # Arguments
+/// - header: &str - the value of the `Range` header (e.g. `bytes=0-499`)
+/// - length: u64 - the length of the representation
+///
+/// # Returns
+/// - Result<Vec<RangeInclusive<u64>>, RangeError> - the satisfiable ranges, in the header order.
+///
+/// Syntax errors, including units other than `bytes`, are reported as [`RangeError::InvalidRangeSyntax`] or
+/// [`RangeError::NotANumber`], while [`RangeError::NotSatisfiable`] is returned if no range is satisfiable, which
+/// maps to `416 Range Not Satisfiable`.
+///
+/// # Example
+///
+/// ```rust
+/// let ranges = range_parser::parse_range_header("bytes=0-499,-500,9500-", 10000).unwrap();
+/// assert_eq!(ranges, vec![0..=499, 9500..=9999, 9500..=9999]);
+///
+/// assert!(matches!(
+///     range_parser::parse_range_header("bytes=10000-", 10000).unwrap_err(),
+///     range_parser::RangeError::NotSatisfiable(10000)
+/// ));
+/// ```
+pub fn parse_range_header(header: &str, length: u64) -> RangeResult<Vec<RangeInclusive<u64>>> {
+    let range_header: RangeHeader = header.parse()?;
+    if !range_header.is_bytes() {
+        return Err(
+            RangeError::InvalidRangeSyntax(Fragment::unlocated(range_header.unit()))
+                .locate(header, 0, 0),
+        );
+    }
+
+    range_header.resolve(length)
+}
+
+/// Parse a single range spec (e.g. `0-499`, `9500-` or `-500`)
+fn parse_byte_range(part: &str) -> RangeResult<ByteRange> {
+    let Some((first, last)) = part.trim().split_once('-') else {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+    };
+
+    match (first.is_empty(), last.is_empty()) {
+        (true, true) => Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part))),
+        (true, false) => parse_position(last).map(ByteRange::Suffix),
+        (false, true) => parse_position(first).map(ByteRange::From),
+        (false, false) => {
+            let first = parse_position(first)?;
+            let last = parse_position(last)?;
+            if first > last {
+                return Err(RangeError::StartBiggerThanEnd(Fragment::unlocated(part)));
+            }
+            Ok(ByteRange::Int(first, last))
+        }
+    }
+}
+
+/// Parse a position, which is made of digits only.
+///
+/// Positions which don't fit in a `u64` are saturated, since they are beyond the end of any representation.
+fn parse_position(position: &str) -> RangeResult<u64> {
+    if position.is_empty() || !position.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(RangeError::NotANumber(Fragment::unlocated(position)));
+    }
+
+    Ok(position.parse().unwrap_or(u64::MAX))
+}
+
+/// Returns whether `c` can be part of a token (RFC 9110, section 5.6.2)
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_range_header() {
+        let header: RangeHeader = "bytes=0-499,500-999,-500,9500-".parse().unwrap();
+        assert_eq!(
+            header.ranges(),
+            &[
+                ByteRange::Int(0, 499),
+                ByteRange::Int(500, 999),
+                ByteRange::Suffix(500),
+                ByteRange::From(9500),
+            ]
+        );
+        assert!(header.is_bytes());
+        assert_eq!(header.to_string(), "bytes=0-499, 500-999, -500, 9500-");
+    }
+
+    #[test]
+    fn should_resolve_ranges_against_length() {
+        assert_eq!(ByteRange::Int(0, 0).resolve(1), Some(0..=0));
+        assert_eq!(ByteRange::Int(5, 10).resolve(5), None);
+        assert_eq!(ByteRange::From(0).resolve(0), None);
+        assert_eq!(ByteRange::Suffix(0).resolve(100), None);
+        assert_eq!(ByteRange::Suffix(500).resolve(100), Some(0..=99));
+        assert_eq!(
+            ByteRange::Int(0, u64::MAX).resolve(u64::MAX),
+            Some(0..=u64::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn should_ignore_unsatisfiable_ranges() {
+        assert_eq!(
+            parse_range_header("bytes=2000-2999, 0-9", 1000).unwrap(),
+            vec![0..=9]
+        );
+        assert_eq!(
+            parse_range_header("bytes=2000-2999, -0", 1000).unwrap_err(),
+            RangeError::NotSatisfiable(1000)
+        );
+    }
+
+    #[test]
+    fn should_accept_whitespace_and_empty_elements() {
+        assert_eq!(
+            parse_range_header(" bytes = 0-1 ,, 4-5 ,", 10).unwrap(),
+            vec![0..=1, 4..=5]
+        );
+        assert_eq!(parse_range_header("BYTES=0-1", 10).unwrap(), vec![0..=1]);
+    }
+
+    #[test]
+    fn should_saturate_huge_positions() {
+        assert_eq!(
+            parse_range_header("bytes=5-99999999999999999999999", 10).unwrap(),
+            vec![5..=9]
+        );
+        assert_eq!(
+            parse_range_header("bytes=-99999999999999999999999", 10).unwrap(),
+            vec![0..=9]
+        );
+    }
+
+    #[test]
+    fn should_not_parse_invalid_range_header() {
+        assert_eq!(
+            parse_range_header("bytes=0-1,x-5", 10).unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 10..11, 1))
+        );
+        assert_eq!(
+            parse_range_header("bytes=0-1,5-2", 10).unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("5-2", 10..13, 1))
+        );
+        assert_eq!(
+            parse_range_header("items=0-1", 10).unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("items", 0..5, 0))
+        );
+        assert!(parse_range_header("0-499", 10).is_err());
+        assert!(parse_range_header("bytes=", 10).is_err());
+        assert!(parse_range_header("bytes=-", 10).is_err());
+        assert!(parse_range_header("bytes=+1-2", 10).is_err());
+        assert!(parse_range_header("bytes=1-2-3", 10).is_err());
+        assert!(parse_range_header("bytes=1 - 2", 10).is_err());
+        assert!(parse_range_header("by tes=1-2", 10).is_err());
+    }
+
+    #[test]
+    fn should_parse_other_units() {
+        let header: RangeHeader = "items=1-5".parse().unwrap();
+        assert_eq!(header.unit(), "items");
+        assert!(!header.is_bytes());
+        assert_eq!(header.resolve(3).unwrap(), vec![1..=2]);
+    }
+}
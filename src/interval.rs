@@ -0,0 +1,355 @@
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::str::FromStr;
+
+use crate::parser::parse_as_t;
+use crate::{Fragment, RangeError, RangeResult, Unit};
+
+/// Separators between the intervals of a union (e.g. `[0, 1) U [2, 3]`)
+const UNION_SEPARATORS: [char; 2] = ['U', '∪'];
+/// Texts of an unbounded start
+const NEGATIVE_INFINITY: [&str; 2] = ["-inf", "-∞"];
+/// Texts of an unbounded end
+const POSITIVE_INFINITY: [&str; 4] = ["inf", "+inf", "∞", "+∞"];
+
+/// An interval in mathematical notation, whose ends can be closed (e.g. `[1, 5]`) or open (e.g. `(1, 5)`).
+///
+/// Contrary to [`crate::parse`], the interval is never expanded, so it's meaningful for floats too.
+///
+/// Unbounded ends are written as `-inf` and `inf` after an open end (e.g. `(-inf, 5]`), so that the displayed
+/// interval can be parsed back. For floats, infinity is always an unbounded end rather than an excluded value.
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Bound;
+///
+/// use range_parser::Interval;
+///
+/// let interval: Interval<f64> = "[0.5, 2)".parse().unwrap();
+/// assert_eq!(interval.start(), &Bound::Included(0.5));
+/// assert_eq!(interval.end(), &Bound::Excluded(2.0));
+/// assert!(interval.contains(&1.99));
+/// assert!(!interval.contains(&2.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: Bound<T>,
+    end: Bound<T>,
+}
+
+impl<T> Interval<T> {
+    /// Create a new [`Interval`] from its bounds
+    pub fn new(start: Bound<T>, end: Bound<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the lower bound of the interval
+    pub fn start(&self) -> &Bound<T> {
+        &self.start
+    }
+
+    /// Returns the upper bound of the interval
+    pub fn end(&self) -> &Bound<T> {
+        &self.end
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: PartialOrd,
+{
+    /// Returns whether the interval contains `value`
+    pub fn contains(&self, value: &T) -> bool {
+        RangeBounds::contains(self, value)
+    }
+
+    /// Returns whether the interval contains no values (e.g. `[1, 1)`)
+    pub fn is_empty(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => false,
+        }
+    }
+}
+
+impl<T> RangeBounds<T> for Interval<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end.as_ref()
+    }
+}
+
+impl<T> fmt::Display for Interval<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.start {
+            Bound::Included(start) => write!(f, "[{start}, ")?,
+            Bound::Excluded(start) => write!(f, "({start}, ")?,
+            Bound::Unbounded => write!(f, "(-inf, ")?,
+        }
+        match &self.end {
+            Bound::Included(end) => write!(f, "{end}]"),
+            Bound::Excluded(end) => write!(f, "{end})"),
+            Bound::Unbounded => write!(f, "inf)"),
+        }
+    }
+}
+
+impl<T> FromStr for Interval<T>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_interval(s).map_err(|err| err.locate(s, 0, 0))
+    }
+}
+
+/// Parse intervals in mathematical notation, optionally joined by `U` or `∪`, into a vector of [`Interval`]
+///
+/// Each interval is written as `[a, b]`, `[a, b)`, `(a, b]` or `(a, b)`, where `[` and `]` include the end,
+/// while `(` and `)` exclude it. An open end can be unbounded, written as `-inf` or `inf` (e.g. `(-inf, 0]`).
+/// Intervals are returned in the input order, without merging them.
+///
+/// # Arguments
+/// - intervals_str: &str - the intervals to parse
+///
+/// # Returns
+/// - Result<Vec<Interval<T>>, RangeError> - the parsed intervals
+///
+/// # Example
+///
+/// ```rust
+/// use std::ops::Bound;
+///
+/// let intervals = range_parser::parse_intervals::<f64>("[0, 1) U (2.5, 3]").unwrap();
+/// assert_eq!(intervals.len(), 2);
+/// assert_eq!(intervals[0].end(), &Bound::Excluded(1.0));
+/// assert_eq!(intervals[1].start(), &Bound::Excluded(2.5));
+/// assert!(intervals.iter().any(|interval| interval.contains(&3.0)));
+/// ```
+pub fn parse_intervals<T>(intervals_str: &str) -> RangeResult<Vec<Interval<T>>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    let mut offset = 0;
+    let mut parts = Vec::new();
+    for (index, separator) in intervals_str.match_indices(UNION_SEPARATORS) {
+        parts.push((offset, &intervals_str[offset..index]));
+        offset = index + separator.len();
+    }
+    parts.push((offset, &intervals_str[offset..]));
+
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, (offset, part))| {
+            parse_interval(part).map_err(|err| err.locate(part, offset, index))
+        })
+        .collect()
+}
+
+/// Parse a single interval (e.g. `[1, 5)`)
+fn parse_interval<T>(part: &str) -> RangeResult<Interval<T>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    // errors report the interval without the whitespace around it
+    let part = part.trim();
+    let invalid = || RangeError::InvalidRangeSyntax(Fragment::unlocated(part));
+
+    let mut chars = part.chars();
+    let (Some(open), Some(close)) = (chars.next(), chars.next_back()) else {
+        return Err(invalid());
+    };
+    let Some((start, end)) = chars.as_str().split_once(',') else {
+        return Err(invalid());
+    };
+    let start: Option<T> = parse_end(start, &NEGATIVE_INFINITY)?;
+    let end: Option<T> = parse_end(end, &POSITIVE_INFINITY)?;
+
+    if let (Some(start), Some(end)) = (&start, &end) {
+        if start > end {
+            return Err(RangeError::StartBiggerThanEnd(Fragment::unlocated(part)));
+        }
+    }
+
+    let start = match (open, start) {
+        ('[', Some(start)) => Bound::Included(start),
+        ('(', Some(start)) => Bound::Excluded(start),
+        ('(', None) => Bound::Unbounded,
+        _ => return Err(invalid()),
+    };
+    let end = match (close, end) {
+        (']', Some(end)) => Bound::Included(end),
+        (')', Some(end)) => Bound::Excluded(end),
+        (')', None) => Bound::Unbounded,
+        _ => return Err(invalid()),
+    };
+
+    Ok(Interval::new(start, end))
+}
+
+/// Parse an end of an interval, returning `None` if it's unbounded
+fn parse_end<T>(text: &str, infinity: &[&str]) -> RangeResult<Option<T>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    let text = text.trim();
+    if infinity.contains(&text) {
+        Ok(None)
+    } else {
+        parse_as_t(text).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_interval_kinds() {
+        assert_eq!(
+            "[1, 5]".parse::<Interval<i32>>().unwrap(),
+            Interval::new(Bound::Included(1), Bound::Included(5))
+        );
+        assert_eq!(
+            "[1, 5)".parse::<Interval<i32>>().unwrap(),
+            Interval::new(Bound::Included(1), Bound::Excluded(5))
+        );
+        assert_eq!(
+            "(0,10]".parse::<Interval<i32>>().unwrap(),
+            Interval::new(Bound::Excluded(0), Bound::Included(10))
+        );
+        assert_eq!(
+            " ( -1.5 , 2 ) ".parse::<Interval<f64>>().unwrap(),
+            Interval::new(Bound::Excluded(-1.5), Bound::Excluded(2.0))
+        );
+    }
+
+    #[test]
+    fn should_parse_union_of_intervals() {
+        assert_eq!(
+            parse_intervals::<f64>("[0,1) U [2,3]").unwrap(),
+            vec![
+                Interval::new(Bound::Included(0.0), Bound::Excluded(1.0)),
+                Interval::new(Bound::Included(2.0), Bound::Included(3.0)),
+            ]
+        );
+        assert_eq!(
+            parse_intervals::<u32>("(0, 1] ∪ (5, 9)").unwrap(),
+            vec![
+                Interval::new(Bound::Excluded(0), Bound::Included(1)),
+                Interval::new(Bound::Excluded(5), Bound::Excluded(9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tell_whether_interval_contains_value() {
+        let interval = Interval::new(Bound::Excluded(0.0), Bound::Included(1.0));
+        assert!(!interval.contains(&0.0));
+        assert!(interval.contains(&0.001));
+        assert!(interval.contains(&1.0));
+        assert!(!interval.contains(&1.001));
+    }
+
+    #[test]
+    fn should_tell_whether_interval_is_empty() {
+        assert!(!"[1, 1]".parse::<Interval<u8>>().unwrap().is_empty());
+        assert!("[1, 1)".parse::<Interval<u8>>().unwrap().is_empty());
+        assert!("(1, 1)".parse::<Interval<u8>>().unwrap().is_empty());
+        assert!(!Interval::new(Bound::Unbounded, Bound::Excluded(0)).is_empty());
+    }
+
+    #[test]
+    fn should_display_interval() {
+        for interval in ["[1, 5]", "[1, 5)", "(1, 5]", "(1.5, 5)"] {
+            assert_eq!(
+                interval.parse::<Interval<f64>>().unwrap().to_string(),
+                interval
+            );
+        }
+        assert_eq!(
+            Interval::new(Bound::Unbounded, Bound::Included(0)).to_string(),
+            "(-inf, 0]"
+        );
+    }
+
+    #[test]
+    fn should_parse_displayed_interval_back() {
+        let intervals = [
+            Interval::new(Bound::Unbounded, Bound::Included(0)),
+            Interval::new(Bound::Excluded(-3), Bound::Unbounded),
+            Interval::new(Bound::Unbounded, Bound::Unbounded),
+            Interval::new(Bound::Included(1), Bound::Excluded(5)),
+        ];
+        for interval in intervals {
+            assert_eq!(
+                interval.to_string().parse::<Interval<i64>>().unwrap(),
+                interval
+            );
+        }
+        assert_eq!(
+            "(-inf, 1.5)".parse::<Interval<f64>>().unwrap(),
+            Interval::new(Bound::Unbounded, Bound::Excluded(1.5))
+        );
+        assert_eq!(
+            parse_intervals::<u8>("(-∞, 3] ∪ (5, +∞)").unwrap(),
+            vec![
+                Interval::new(Bound::Unbounded, Bound::Included(3)),
+                Interval::new(Bound::Excluded(5), Bound::Unbounded),
+            ]
+        );
+        assert!("[-inf, 0]".parse::<Interval<i32>>().is_err());
+        assert!("(0, inf]".parse::<Interval<i32>>().is_err());
+        assert!("(inf, 0)".parse::<Interval<i32>>().is_err());
+    }
+
+    #[test]
+    fn should_locate_errors_in_union() {
+        assert_eq!(
+            parse_intervals::<u32>("[0, 1] U [2, x]").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 13..14, 1))
+        );
+        assert_eq!(
+            parse_intervals::<u32>("[0, 1] U [5, 2]").unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("[5, 2]", 9..15, 1))
+        );
+        assert_eq!(
+            parse_intervals::<u32>("[0, 1] U {2, 3}").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("{2, 3}", 9..15, 1))
+        );
+        assert_eq!(
+            " [5, 2] ".parse::<Interval<u32>>().unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("[5, 2]", 1..7, 0))
+        );
+        assert_eq!(
+            "[1,  x )".parse::<Interval<u32>>().unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 5..6, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_parse_malformed_intervals() {
+        assert!("[1, 5".parse::<Interval<u8>>().is_err());
+        assert!("[1 5]".parse::<Interval<u8>>().is_err());
+        assert!("[1, 2, 3]".parse::<Interval<u8>>().is_err());
+        assert!("[".parse::<Interval<u8>>().is_err());
+        assert!("".parse::<Interval<u8>>().is_err());
+        assert!(parse_intervals::<u8>("[1, 2] U").is_err());
+    }
+}
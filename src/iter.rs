@@ -0,0 +1,384 @@
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use crate::segment::{Segment, Selection};
+use crate::Unit;
+
+/// A lazy iterator over the values of a parsed range.
+///
+/// The range string is fully validated when the iterator is created, but values are only produced when requested,
+/// so even huge ranges (e.g. `0-4000000000`) can be iterated without allocating them.
+///
+/// Use [`crate::parse_iter`] or [`crate::parse_iter_with`] to create it.
+///
+/// Excluded values (e.g. `!5`) are skipped while iterating, so the size hint is exact only when the range string
+/// has no exclusions.
+///
+/// # Example
+///
+/// ```rust
+/// let mut iter = range_parser::parse_iter::<u64>("1-3,7").unwrap();
+/// assert_eq!(iter.size_hint(), (4, Some(4)));
+/// assert_eq!(iter.next(), Some(1));
+/// assert_eq!(iter.next_back(), Some(7));
+/// assert_eq!(iter.collect::<Vec<u64>>(), vec![2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeIter<T> {
+    spans: VecDeque<Span<T>>,
+    /// Excluded segments, with the position of the segment in the range string
+    exclusions: Vec<(usize, Segment<T>)>,
+}
+
+/// The remaining values of a segment, expressed as the steps from `start`
+#[derive(Debug, Clone)]
+struct Span<T> {
+    /// Position of the segment in the range string
+    position: usize,
+    start: T,
+    step: T,
+    /// Whether `start` is multiplied by `step` instead of being incremented by it
+    geometric: bool,
+    /// End of a stepped range, which replaces the last value when floats round to it from above
+    end: Option<T>,
+    /// Index of the last value
+    last: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<T> Span<T>
+where
+    T: Unit,
+{
+    fn new(position: usize, segment: Segment<T>) -> Self {
+        let (start, end, step) = match segment {
+            Segment::Value(value) => {
+                return Self {
+                    position,
+                    start: value,
+                    step: T::unit(),
+                    geometric: false,
+                    end: None,
+                    last: 0,
+                    front: 0,
+                    back: 0,
+                }
+            }
+            Segment::Range(start, end) => (start, end, T::unit()),
+            Segment::SteppedRange(start, end, step) => (start, end, step),
+            Segment::GeometricRange(start, end, factor) => {
+                // factors have already been checked while parsing the range
+                let back = T::scales_between_by(&start, &end, &factor).unwrap_or_default();
+                return Self {
+                    position,
+                    start,
+                    step: factor,
+                    geometric: true,
+                    end: None,
+                    last: back,
+                    front: 0,
+                    back,
+                };
+            }
+        };
+        // steps have already been checked while parsing the range
+        let back = T::steps_between_by(&start, &end, &step).unwrap_or_default();
+        Self {
+            position,
+            start,
+            step,
+            geometric: false,
+            end: Some(end),
+            last: back,
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl<T> Span<T>
+where
+    T: PartialOrd + Unit,
+{
+    /// Returns the value at `index` steps from `start`
+    fn value(&mut self, index: usize) -> Option<T> {
+        let value = self.value_at(index)?;
+        // the last value is returned only once, so the end can be moved out of the span
+        match self.end.take_if(|end| index == self.last && value > *end) {
+            Some(end) => Some(end),
+            None => Some(value),
+        }
+    }
+
+    /// Move the front to the first value which is not smaller than `low`, without producing the values before it.
+    ///
+    /// Returns `false` if all the values are smaller than `low`.
+    fn skip_below(&mut self, low: &T) -> bool {
+        let index = if self.geometric {
+            T::scales_between_by(&self.start, low, &self.step)
+        } else {
+            T::steps_between_by(&self.start, low, &self.step)
+        };
+        // `low` is smaller than the start
+        let Some(index) = index else {
+            return true;
+        };
+        let below = match self.value_at(index) {
+            // floats which overshoot the end are replaced by it
+            Some(value)
+                if index == self.last && self.end.as_ref().is_some_and(|end| value > *end) =>
+            {
+                self.end.as_ref().is_some_and(|end| end < low)
+            }
+            Some(value) => value < *low,
+            None => true,
+        };
+        match index.checked_add(usize::from(below)) {
+            Some(front) if front <= self.back => {
+                self.front = front;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the value at `index` steps from `start`, without replacing it with the end
+    fn value_at(&self, index: usize) -> Option<T> {
+        if self.geometric {
+            self.start.scale_by(&self.step, index)
+        } else {
+            self.start.forward_by(&self.step, index)
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        (self.back - self.front).checked_add(1)
+    }
+}
+
+impl<T> RangeIter<T>
+where
+    T: Unit,
+{
+    /// Create a new [`RangeIter`] from validated segments
+    pub(crate) fn new(segments: Vec<Segment<T>>) -> Self {
+        Self::from_selections(segments.into_iter().map(Selection::Include).collect())
+    }
+
+    /// Create a new [`RangeIter`] over the values of a validated segment which are not smaller than `low`
+    pub(crate) fn from_value(segment: Segment<T>, low: &T) -> Self
+    where
+        T: PartialOrd,
+    {
+        let mut span = Span::new(0, segment);
+        let spans = if span.skip_below(low) {
+            VecDeque::from([span])
+        } else {
+            VecDeque::new()
+        };
+
+        Self {
+            spans,
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Create a new [`RangeIter`] from validated segments, which may exclude values
+    pub(crate) fn from_selections(selections: Vec<Selection<T>>) -> Self {
+        let mut spans = VecDeque::new();
+        let mut exclusions = Vec::new();
+        for (position, selection) in selections.into_iter().enumerate() {
+            match selection {
+                Selection::Include(segment) => spans.push_back(Span::new(position, segment)),
+                Selection::Exclude(segment) => exclusions.push((position, segment)),
+            }
+        }
+
+        Self { spans, exclusions }
+    }
+}
+
+impl<T> RangeIter<T>
+where
+    T: PartialOrd + Unit,
+{
+    /// Returns whether `value`, produced by the segment at `position`, is removed by a following exclusion
+    fn is_excluded(&self, position: usize, value: &T) -> bool {
+        self.exclusions
+            .iter()
+            .any(|(excluded_at, segment)| *excluded_at > position && segment.contains(value))
+    }
+}
+
+impl<T> Iterator for RangeIter<T>
+where
+    T: PartialOrd + Unit,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let span = self.spans.front_mut()?;
+            let position = span.position;
+            let value = span.value(span.front);
+            if span.front == span.back {
+                self.spans.pop_front();
+            } else {
+                span.front += 1;
+            }
+            match value {
+                Some(value) if !self.is_excluded(position, &value) => return Some(value),
+                _ => continue,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self
+            .spans
+            .iter()
+            .try_fold(0usize, |acc, span| acc.checked_add(span.len()?));
+
+        match len {
+            Some(len) if self.exclusions.is_empty() => (len, Some(len)),
+            // any value may be excluded
+            Some(len) => (0, Some(len)),
+            None if self.exclusions.is_empty() => (usize::MAX, None),
+            None => (0, None),
+        }
+    }
+}
+
+impl<T> DoubleEndedIterator for RangeIter<T>
+where
+    T: PartialOrd + Unit,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let span = self.spans.back_mut()?;
+            let position = span.position;
+            let value = span.value(span.back);
+            if span.front == span.back {
+                self.spans.pop_back();
+            } else {
+                span.back -= 1;
+            }
+            match value {
+                Some(value) if !self.is_excluded(position, &value) => return Some(value),
+                _ => continue,
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for RangeIter<T> where T: PartialOrd + Unit {}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_iterate_over_segments() {
+        let iter = RangeIter::new(vec![
+            Segment::Value(1),
+            Segment::Range(3, 5),
+            Segment::Value(2),
+        ]);
+        assert_eq!(iter.collect::<Vec<u64>>(), vec![1, 3, 4, 5, 2]);
+    }
+
+    #[test]
+    fn should_iterate_backwards() {
+        let iter = RangeIter::new(vec![Segment::Range(-2, 1), Segment::Value(7)]);
+        assert_eq!(iter.rev().collect::<Vec<i32>>(), vec![7, 1, 0, -1, -2]);
+    }
+
+    #[test]
+    fn should_iterate_from_both_ends() {
+        let mut iter = RangeIter::new(vec![Segment::Range(1u8, 4)]);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn should_give_exact_size_hint() {
+        let mut iter = RangeIter::new(vec![Segment::Range(0u64, 9), Segment::Value(20)]);
+        assert_eq!(iter.size_hint(), (11, Some(11)));
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.size_hint(), (9, Some(9)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn should_not_allocate_huge_ranges() {
+        let mut iter = RangeIter::new(vec![Segment::Range(0u64, 4_000_000_000)]);
+        assert_eq!(iter.size_hint(), (4_000_000_001, Some(4_000_000_001)));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(4_000_000_000));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn should_saturate_size_hint_on_overflow() {
+        let iter = RangeIter::new(vec![Segment::Range(0u64, u64::MAX)]);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn should_iterate_stepped_ranges() {
+        let iter = RangeIter::new(vec![Segment::SteppedRange(0u32, 10, 4), Segment::Value(1)]);
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        assert_eq!(iter.clone().collect::<Vec<u32>>(), vec![0, 4, 8, 1]);
+        assert_eq!(iter.rev().collect::<Vec<u32>>(), vec![1, 8, 4, 0]);
+    }
+
+    #[test]
+    fn should_iterate_geometric_ranges() {
+        let iter = RangeIter::new(vec![Segment::GeometricRange(2u32, 100, 3)]);
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        assert_eq!(iter.clone().collect::<Vec<u32>>(), vec![2, 6, 18, 54]);
+        assert_eq!(iter.rev().collect::<Vec<u32>>(), vec![54, 18, 6, 2]);
+    }
+
+    #[test]
+    fn should_iterate_from_value() {
+        let iter = RangeIter::from_value(Segment::SteppedRange(0u64, 4_000_000_000, 3), &10);
+        assert_eq!(iter.take(3).collect::<Vec<u64>>(), vec![12, 15, 18]);
+        let iter = RangeIter::from_value(Segment::GeometricRange(1u32, 1000, 10), &100);
+        assert_eq!(iter.collect::<Vec<u32>>(), vec![100, 1000]);
+        let iter = RangeIter::from_value(Segment::SteppedRange(5u8, 20, 5), &0);
+        assert_eq!(iter.collect::<Vec<u8>>(), vec![5, 10, 15, 20]);
+        let mut iter = RangeIter::from_value(Segment::SteppedRange(5u8, 20, 5), &21);
+        assert_eq!(iter.next(), None);
+        let iter = RangeIter::from_value(Segment::SteppedRange(0.0f64, 0.3, 0.1), &0.25);
+        assert_eq!(iter.collect::<Vec<f64>>(), vec![0.3]);
+    }
+
+    #[test]
+    fn should_iterate_float_ranges_from_both_ends() {
+        let iter = RangeIter::new(vec![Segment::Range(0.5f64, 3.0)]);
+        assert_eq!(iter.clone().collect::<Vec<f64>>(), vec![0.5, 1.5, 2.5]);
+        assert_eq!(iter.rev().collect::<Vec<f64>>(), vec![2.5, 1.5, 0.5]);
+    }
+
+    #[test]
+    fn should_skip_excluded_values() {
+        let iter = RangeIter::from_selections(vec![
+            Selection::Include(Segment::Range(1u32, 6)),
+            Selection::Exclude(Segment::Range(2, 3)),
+            Selection::Include(Segment::Value(2)),
+            Selection::Exclude(Segment::SteppedRange(4, 6, 2)),
+        ]);
+        assert_eq!(iter.size_hint(), (0, Some(7)));
+        assert_eq!(iter.clone().collect::<Vec<u32>>(), vec![1, 5, 2]);
+        assert_eq!(iter.rev().collect::<Vec<u32>>(), vec![2, 5, 1]);
+    }
+}
@@ -0,0 +1,232 @@
+//! A lexer for the values and ranges of a range string segment
+//!
+//! The grammar of a segment, once the step has been split off, is:
+//!
+//! ```text
+//! segment   := value | [value] separator [value]
+//! value     := ws* [sign] body ws*
+//! sign      := "+" | "-"
+//! body      := (any char which is not whitespace and doesn't start the separator | exponent)+
+//! exponent  := mantissa ("e" | "E") sign digit
+//! ```
+//!
+//! A sign is part of a value when it's followed by the body of the value, so with `-` as separator `-1--3`
+//! goes from `-1` to `-3`. The sign of an exponent (e.g. `1e-3`) is never a separator, as long as it follows
+//! a mantissa made only of digits and `.` and it's followed by a digit.
+
+use crate::{Fragment, RangeError, RangeResult};
+
+/// The syntax of a segment, as recognized by the lexer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Syntax<'a> {
+    /// A single value (e.g. `-5`)
+    Value(&'a str),
+    /// A range with its optional start and end (e.g. `1-5` or `5-`)
+    Range(Option<&'a str>, Option<&'a str>),
+}
+
+/// Split a segment into its values and range separator
+pub(crate) fn lex<'a>(segment: &'a str, separator: &str) -> RangeResult<Syntax<'a>> {
+    let invalid = || RangeError::InvalidRangeSyntax(Fragment::unlocated(segment));
+    let mut lexer = Lexer {
+        input: segment,
+        pos: 0,
+        separator,
+    };
+
+    let start = lexer.value();
+    if lexer.is_at_end() {
+        return start.map(Syntax::Value).ok_or_else(invalid);
+    }
+    if !lexer.eat_separator() {
+        return Err(invalid());
+    }
+    let end = lexer.value();
+    if !lexer.is_at_end() {
+        return Err(invalid());
+    }
+
+    Ok(Syntax::Range(start, end))
+}
+
+struct Lexer<'a, 's> {
+    input: &'a str,
+    pos: usize,
+    separator: &'s str,
+}
+
+impl<'a> Lexer<'a, '_> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn at_separator(&self) -> bool {
+        !self.separator.is_empty() && self.rest().starts_with(self.separator)
+    }
+
+    fn eat_separator(&mut self) -> bool {
+        if self.at_separator() {
+            self.pos += self.separator.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Read a value, surrounded by optional whitespace, returning `None` if there is no value
+    fn value(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        if self.at_sign() {
+            self.bump();
+        }
+        let body = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || (self.at_separator() && !self.at_exponent_sign(body)) {
+                break;
+            }
+            self.bump();
+        }
+
+        let value = &self.input[start..self.pos];
+        self.skip_whitespace();
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Returns whether the lexer is at the sign of a value, which must be followed by its body
+    fn at_sign(&self) -> bool {
+        let mut chars = self.rest().chars();
+        if !matches!(chars.next(), Some('+' | '-')) {
+            return false;
+        }
+        let after = chars.as_str();
+        after.chars().next().is_some_and(|c| !c.is_whitespace())
+            && (self.separator.is_empty() || !after.starts_with(self.separator))
+    }
+
+    /// Returns whether the lexer is at the sign of the exponent of the number started at `body`
+    fn at_exponent_sign(&self, body: usize) -> bool {
+        let mut rest = self.rest().chars();
+        if !matches!(rest.next(), Some('+' | '-'))
+            || !rest.next().is_some_and(|c| c.is_ascii_digit())
+        {
+            return false;
+        }
+        let Some(mantissa) = self.input[body..self.pos]
+            .strip_suffix('e')
+            .or_else(|| self.input[body..self.pos].strip_suffix('E'))
+        else {
+            return false;
+        };
+
+        mantissa.chars().any(|c| c.is_ascii_digit())
+            && mantissa.chars().all(|c| c.is_ascii_digit() || c == '.')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_lex_values() {
+        assert_eq!(lex("5", "-").unwrap(), Syntax::Value("5"));
+        assert_eq!(lex(" -5 ", "-").unwrap(), Syntax::Value("-5"));
+        assert_eq!(lex("+5", "-").unwrap(), Syntax::Value("+5"));
+        assert_eq!(lex("1e-3", "-").unwrap(), Syntax::Value("1e-3"));
+        assert_eq!(lex("-2.5E+10", "+").unwrap(), Syntax::Value("-2.5E+10"));
+    }
+
+    #[test]
+    fn should_lex_ranges() {
+        assert_eq!(
+            lex("1-3", "-").unwrap(),
+            Syntax::Range(Some("1"), Some("3"))
+        );
+        assert_eq!(
+            lex(" 1 - 3 ", "-").unwrap(),
+            Syntax::Range(Some("1"), Some("3"))
+        );
+        assert_eq!(
+            lex("-5--3", "-").unwrap(),
+            Syntax::Range(Some("-5"), Some("-3"))
+        );
+        assert_eq!(
+            lex("+1-+5", "-").unwrap(),
+            Syntax::Range(Some("+1"), Some("+5"))
+        );
+        assert_eq!(
+            lex("-5..-3", "..").unwrap(),
+            Syntax::Range(Some("-5"), Some("-3"))
+        );
+        assert_eq!(
+            lex("1 to 5", "to").unwrap(),
+            Syntax::Range(Some("1"), Some("5"))
+        );
+        assert_eq!(
+            lex("a-z", "-").unwrap(),
+            Syntax::Range(Some("a"), Some("z"))
+        );
+    }
+
+    #[test]
+    fn should_lex_exponents() {
+        assert_eq!(
+            lex("1e-3-2e-3", "-").unwrap(),
+            Syntax::Range(Some("1e-3"), Some("2e-3"))
+        );
+        assert_eq!(
+            lex("-1.5e-3--1e-3", "-").unwrap(),
+            Syntax::Range(Some("-1.5e-3"), Some("-1e-3"))
+        );
+        assert_eq!(
+            lex("1e+3+2e+3", "+").unwrap(),
+            Syntax::Range(Some("1e+3"), Some("2e+3"))
+        );
+        // not an exponent, because the mantissa is not a number
+        assert_eq!(
+            lex("e-f", "-").unwrap(),
+            Syntax::Range(Some("e"), Some("f"))
+        );
+    }
+
+    #[test]
+    fn should_lex_open_ranges() {
+        assert_eq!(lex("5-", "-").unwrap(), Syntax::Range(Some("5"), None));
+        assert_eq!(lex("..5", "..").unwrap(), Syntax::Range(None, Some("5")));
+        assert_eq!(lex("--5", "-").unwrap(), Syntax::Range(None, Some("-5")));
+        assert_eq!(lex("..", "..").unwrap(), Syntax::Range(None, None));
+    }
+
+    #[test]
+    fn should_not_lex_invalid_syntax() {
+        assert!(lex("", "-").is_err());
+        assert!(lex("  ", "-").is_err());
+        assert!(lex("1 2", "-").is_err());
+        assert!(lex("1-2-3", "-").is_err());
+        assert!(lex("1-2 3", "-").is_err());
+    }
+}
@@ -57,40 +57,168 @@
 //! assert_eq!(range, vec![-2, 0, 1, 2, 3, -1, 7]);
 //! ```
 //!
+//! ### Parse a range with a step
+//!
+//! ```rust
+//! let range: Vec<u32> = range_parser::parse("0-100:25").unwrap();
+//! assert_eq!(range, vec![0, 25, 50, 75, 100]);
+//! ```
+//!
+//! ### Parse a range divided into evenly spaced points
+//!
+//! ```rust
+//! let range: Vec<f64> = range_parser::parse("0-1#5").unwrap();
+//! assert_eq!(range, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+//! ```
+//!
+//! ### Parse a geometric range
+//!
+//! ```rust
+//! let range: Vec<u32> = range_parser::parse("1-1000*10").unwrap();
+//! assert_eq!(range, vec![1, 10, 100, 1000]);
+//! ```
+//!
+//! ### Exclude values from a range
+//!
+//! ```rust
+//! let range: Vec<u32> = range_parser::parse("1-10,!3-8,5").unwrap();
+//! assert_eq!(range, vec![1, 2, 9, 10, 5]);
+//! ```
+//!
+//! ### Parse Rust-style ranges
+//!
+//! ```rust
+//! let parser = range_parser::RangeParser::rust();
+//! assert_eq!(parser.parse::<u32>("0..3,5..=7").unwrap(), vec![0, 1, 2, 5, 6, 7]);
+//! assert_eq!(parser.parse_ranges::<u32>("0..3,5..=7").unwrap(), vec![0..3, 5..8]);
+//! ```
+//!
+//! ### Configure a reusable parser
+//!
+//! ```rust
+//! use range_parser::RangeParser;
+//!
+//! let parser = RangeParser::new().value_separator(";").sort(true).dedup(true);
+//! let range: Vec<u32> = parser.parse("8;1-3;2").unwrap();
+//! assert_eq!(range, vec![1, 2, 3, 8]);
+//! ```
+//!
+//! ### Iterate over a range lazily
+//!
+//! ```rust
+//! let mut iter = range_parser::parse_iter::<u64>("0-4000000000").unwrap();
+//! assert_eq!(iter.next(), Some(0));
+//! assert_eq!(iter.next_back(), Some(4000000000));
+//! ```
+//!
+//! ### Format values back into a range string
+//!
+//! ```rust
+//! let range_str = range_parser::format(&[1, 3, 4, 5, 2]).unwrap();
+//! assert_eq!(range_str, "1,3-5,2");
+//! ```
+//!
+//! ### Parse intervals in mathematical notation
+//!
+//! ```rust
+//! let intervals = range_parser::parse_intervals::<f64>("[0, 1) U (2, 3]").unwrap();
+//! assert!(intervals[0].contains(&0.5));
+//! assert!(!intervals[1].contains(&2.0));
+//! ```
+//!
+//! ### Parse an HTTP Range header
+//!
+//! ```rust
+//! let ranges = range_parser::parse_range_header("bytes=0-499,-500", 10000).unwrap();
+//! assert_eq!(ranges, vec![0..=499, 9500..=9999]);
+//! ```
+//!
+//! ### Parse a Linux cpulist
+//!
+//! ```rust
+//! let cpus = range_parser::parse_cpulist("0-15:2/4\n", 16).unwrap();
+//! assert_eq!(range_parser::format_cpulist(&cpus), "0-1,4-5,8-9,12-13\n");
+//! assert_eq!(range_parser::format_cpumask(&cpus, 16), "3333\n");
+//! ```
+//!
+//! ### Parse a cron field
+//!
+//! ```rust
+//! use range_parser::CronField;
+//!
+//! let days = CronField::DayOfWeek.parse("MON-FRI").unwrap();
+//! assert_eq!(days.intervals(), &[1..=5]);
+//! ```
+//!
+//! ### Parse ranges wrapping around a cyclic domain
+//!
+//! ```rust
+//! let weekdays = range_parser::CyclicDomain::weekdays();
+//! assert_eq!(weekdays.parse("fri-mon").unwrap(), vec![5, 6, 0, 1]);
+//! ```
+//!
+//! ### Expand a hostlist
+//!
+//! ```rust
+//! let hosts = range_parser::expand_hostlist("rack[1-2]-n[01-02]").unwrap();
+//! assert_eq!(hosts, vec!["rack1-n01", "rack1-n02", "rack2-n01", "rack2-n02"]);
+//! assert_eq!(range_parser::compress_hostlist(&hosts), "rack[1-2]-n[01-02]");
+//! ```
+//!
+//! ### Parse a range into a set of intervals
+//!
+//! ```rust
+//! use range_parser::RangeSet;
+//!
+//! let set: RangeSet<u64> = RangeSet::parse("1,3-5,2,8").unwrap();
+//! assert_eq!(set.intervals(), &[1..=5, 8..=8]);
+//! assert!(set.contains(&4));
+//! ```
+//!
 
+mod cpulist;
+mod cron;
+mod cyclic;
+mod error;
+mod format;
+mod hostlist;
+mod http;
+mod interval;
+mod iter;
+mod lexer;
+mod limits;
+#[cfg(feature = "num-traits")]
+mod numeric;
+mod parser;
+mod segment;
+mod set;
 mod unit;
 
 use std::cmp::{PartialEq, PartialOrd};
-use std::ops::Add;
 use std::str::FromStr;
 
-use thiserror::Error;
-
+pub use self::cpulist::{format_cpulist, format_cpumask, parse_cpulist, parse_cpumask};
+pub use self::cron::{CronField, CronSchedule};
+pub use self::cyclic::CyclicDomain;
+pub use self::error::{Fragment, RangeError, RangeResult};
+pub use self::format::{format, format_with, format_with_options, FormatOptions};
+pub use self::hostlist::{compress_hostlist, expand_hostlist};
+pub use self::http::{parse_range_header, ByteRange, RangeHeader};
+pub use self::interval::{parse_intervals, Interval};
+pub use self::iter::RangeIter;
+pub use self::limits::Limits;
+#[cfg(feature = "num-traits")]
+pub use self::numeric::Numeric;
+pub use self::parser::RangeParser;
+pub use self::set::RangeSet;
 pub use self::unit::Unit;
 
-const AMBIGOUS_RANGE_SEPARATORS: &[&str] = &["--"];
-
-/// Parse error
-#[derive(Debug, Error, Clone, PartialEq, Eq)]
-pub enum RangeError {
-    #[error("Invalid range syntax: {0}")]
-    InvalidRangeSyntax(String),
-    #[error("Not a number: {0}")]
-    NotANumber(String),
-    #[error("Value and range separators cannot be the same")]
-    SeparatorsMustBeDifferent,
-    #[error("Start of the range cannot be bigger than the end: {0}")]
-    StartBiggerThanEnd(String),
-    #[error("Ambiguous separator: {0}")]
-    AmbiguousSeparator(String),
-}
-
-/// Parse result
-pub type RangeResult<T> = Result<T, RangeError>;
+use self::parser::check_countable;
+use self::segment::Selection;
 
 /// Parse a range string to a vector of any kind of number
 ///
-/// The type T must implement the `FromStr`, `Add`, `PartialEq`, `PartialOrd`, `Unit` and `Copy` traits.
+/// The type T must implement the `FromStr`, `PartialEq`, `PartialOrd`, `Unit` and `Clone` traits.
 ///
 /// # Arguments
 /// - range_str: &str - the range string to parse
@@ -112,14 +240,14 @@ pub type RangeResult<T> = Result<T, RangeError>;
 /// ```
 pub fn parse<T>(range_str: &str) -> RangeResult<Vec<T>>
 where
-    T: FromStr + Add<Output = T> + PartialEq + PartialOrd + Unit + Copy,
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
 {
     parse_with(range_str, ",", "-")
 }
 
 /// Parse a range string to a vector of any kind of numbers with custom separators
 ///
-/// The type T must implement the `FromStr`, `Add`, `PartialEq`, `PartialOrd`, `Unit` and `Copy` traits.
+/// The type T must implement the `FromStr`, `PartialEq`, `PartialOrd`, `Unit` and `Clone` traits.
 ///
 /// # Arguments
 /// - range_str: &str - the range string to parse
@@ -135,6 +263,33 @@ where
 /// The range separator cannot be the same as the value separator, and it cannot be one of the following: `--`,
 /// because it's ambiguous since it couldn't resolve negative numbers.
 ///
+/// # Steps
+///
+/// A range can be followed by `:` and a step (e.g. `0-10:5`), to advance by the step instead of by one unit.
+/// Use [`parse_with_separators`] to customize the step separator. Steps are disabled if `:` is part of the value or
+/// range separator, so `1:2:3` can still be parsed with `:` as value separator. Likewise, point counts (e.g.
+/// `0-1#5`) and factors (e.g. `1-1000*10`) are disabled if `#` or `*` are part of the value or range separator.
+///
+/// # Grammar
+///
+/// Each segment is either a value or a range, whose start or end can be omitted only by [`parse_bounded`]:
+///
+/// ```text
+/// segment   := value | [value] range_separator [value]
+/// value     := ws* ["+" | "-"] body ws*
+/// ```
+///
+/// A sign is part of a value when it's directly followed by the value, so `-5--3` goes from `-5` to `-3` and
+/// `+1-+3` from `1` to `3`. The sign of a float exponent is never a range separator, so `1e-3-2e-3` goes from
+/// `0.001` to `0.002`. Values can be surrounded by whitespace, but can't contain it.
+///
+/// # Exclusions
+///
+/// A segment preceded by `!` (e.g. `!5-8`) removes its values from the values accumulated by the previous
+/// segments. Exclusions are applied in order, so `1-9,!5,5` still contains `5`, while `!5,1-9` excludes nothing.
+/// Use [`RangeParser::exclusion_marker`] to customize the marker. Exclusions are disabled if `!` is part of the value
+/// or range separator.
+///
 /// # Example
 ///
 /// ```rust
@@ -147,113 +302,255 @@ pub fn parse_with<T>(
     range_separator: &str,
 ) -> RangeResult<Vec<T>>
 where
-    T: FromStr + Add<Output = T> + PartialEq + PartialOrd + Unit + Copy,
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
 {
-    if value_separator == range_separator {
-        return Err(RangeError::SeparatorsMustBeDifferent);
-    }
-    if AMBIGOUS_RANGE_SEPARATORS.contains(&range_separator) {
-        return Err(RangeError::AmbiguousSeparator(range_separator.to_string()));
-    }
-
-    let mut range = Vec::new();
-
-    for part in range_str.split(value_separator) {
-        parse_part(&mut range, part, range_separator)?;
-    }
+    RangeParser::with_separators(value_separator, range_separator).parse(range_str)
+}
 
-    Ok(range)
+/// Parse a range string to a vector of any kind of numbers with custom value, range and step separators
+///
+/// The step is added to the start of the range until the end is exceeded, so the end is not part of the range
+/// if it can't be reached. The step must be positive.
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+/// - value_separator: &str - the separator for single values
+/// - range_separator: &str - the separator for ranges
+/// - step_separator: &str - the separator between a range and its step
+///
+/// # Returns
+/// - Result<Vec<T>, RangeError> - the parsed range
+///
+/// # Example
+///
+/// ```rust
+/// let range: Vec<u32> = range_parser::parse_with_separators("0-20/5;1", ";", "-", "/").unwrap();
+/// assert_eq!(range, vec![0, 5, 10, 15, 20, 1]);
+///
+/// let range: Vec<f64> = range_parser::parse_with_separators("0..1 by 0.25", ",", "..", " by ").unwrap();
+/// assert_eq!(range, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+/// ```
+pub fn parse_with_separators<T>(
+    range_str: &str,
+    value_separator: &str,
+    range_separator: &str,
+    step_separator: &str,
+) -> RangeResult<Vec<T>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    RangeParser::with_separators(value_separator, range_separator)
+        .step_separator(step_separator)
+        .parse(range_str)
 }
 
-/// Parse a range part to a vector of T
-fn parse_part<T>(acc: &mut Vec<T>, part: &str, range_separator: &str) -> RangeResult<()>
+/// Parse a range string, which may contain open-ended ranges, to a vector of values between `min` and `max`
+///
+/// Besides the syntax accepted by [`parse`], the range string may contain:
+///
+/// - `a-`: a range from `a` to `max`
+/// - `-b`: a range from `min` to `b`, but only if `-b` is not a valid number for `T` (e.g. `T` is unsigned),
+///   because otherwise it's a negative number
+/// - `*`: the whole domain, from `min` to `max`
+///
+/// Any value out of `min..=max` is rejected with [`RangeError::OutOfBounds`].
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+/// - min: T - the lowest value of the domain
+/// - max: T - the highest value of the domain
+///
+/// # Returns
+/// - Result<Vec<T>, RangeError> - the parsed range
+///
+/// # Example
+///
+/// ```rust
+/// // select columns as `cut -f` does
+/// let columns: Vec<usize> = range_parser::parse_bounded("-2,5,7-", 1, 8).unwrap();
+/// assert_eq!(columns, vec![1, 2, 5, 7, 8]);
+///
+/// let pages: Vec<u32> = range_parser::parse_bounded("*", 1, 4).unwrap();
+/// assert_eq!(pages, vec![1, 2, 3, 4]);
+/// ```
+pub fn parse_bounded<T>(range_str: &str, min: T, max: T) -> RangeResult<Vec<T>>
 where
-    T: FromStr + Add<Output = T> + PartialEq + PartialOrd + Unit + Copy,
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
 {
-    if part.contains(range_separator) {
-        parse_value_range(acc, part, range_separator)?;
-    } else {
-        acc.push(parse_as_t(part)?);
-    }
-    Ok(())
+    parse_bounded_with(range_str, min, max, ",", "-")
 }
 
-/// Parse value range to a vector of T
+/// Parse a range string, which may contain open-ended ranges, to a vector of values between `min` and `max`
+/// with custom separators
+///
+/// When the range separator is not `-`, a range starting with the range separator (e.g. `..5`) always
+/// goes from `min`. See [`parse_bounded`] for the syntax of open-ended ranges.
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+/// - min: T - the lowest value of the domain
+/// - max: T - the highest value of the domain
+/// - value_separator: &str - the separator for single values
+/// - range_separator: &str - the separator for ranges
+///
+/// # Returns
+/// - Result<Vec<T>, RangeError> - the parsed range
+///
+/// # Example
 ///
-/// If the range is `1-3`, it will add 1, 2, 3 to the accumulator.
-/// If the range starts with `-`, but has not a number before it, it will consider it as a negative number.
-fn parse_value_range<T>(acc: &mut Vec<T>, part: &str, range_separator: &str) -> RangeResult<()>
+/// ```rust
+/// let range: Vec<i32> = range_parser::parse_bounded_with("..-8;0;5..", -10, 7, ";", "..").unwrap();
+/// assert_eq!(range, vec![-10, -9, -8, 0, 5, 6, 7]);
+/// ```
+pub fn parse_bounded_with<T>(
+    range_str: &str,
+    min: T,
+    max: T,
+    value_separator: &str,
+    range_separator: &str,
+) -> RangeResult<Vec<T>>
 where
-    T: FromStr + Add<Output = T> + PartialEq + PartialOrd + Unit + Copy,
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
 {
-    let parts: Vec<&str> = part.split(range_separator).collect();
-
-    // here it gets a bit tricky
-    // because for example we could have `-1-3` which is a valid range
-    // or `-5--3` which is also a valid range. So we need to find a way to tell what is dividing the range exactly
-    // so let's calculate the first part index
-    let (start, end): (T, T) = match parts.len() {
-        2 if parts[0].is_empty() && range_separator == "-" => {
-            // if the first part is empty, it means it's a negative number
-            let end = format!("-{}", parts[1]);
-            let end: T = parse_as_t(&end)?;
-            acc.push(end);
-            return Ok(());
-        }
-        // 2 positive numbers (or also negative if range_separator is not `-`)
-        2 => {
-            let start = parts[0];
-            let end = parts[1];
-            let start: T = parse_as_t(start)?;
-            let end: T = parse_as_t(end)?;
-            (start, end)
-        }
-        // 3 is tricky, because it could be both `-1-2` or `1--3`, but the second case is invalid actually,
-        // because start cannot be greater than end
-        3 if parts[0].is_empty() && range_separator == "-" => {
-            let start = format!("-{}", parts[1]);
-            let end = parts[2];
-            let start: T = parse_as_t(&start)?;
-            let end: T = parse_as_t(end)?;
-            (start, end)
-        }
-        3 => return Err(RangeError::StartBiggerThanEnd(part.to_string())),
-        4 if range_separator == "-" => {
-            let start = format!("-{}", parts[1]);
-            let end = format!("-{}", parts[3]);
-            let start: T = parse_as_t(&start)?;
-            let end: T = parse_as_t(&end)?;
-            (start, end)
-        }
-        _ => return Err(RangeError::InvalidRangeSyntax(part.to_string())),
-    };
+    RangeParser::with_separators(value_separator, range_separator)
+        .parse_bounded(range_str, min, max)
+}
 
-    // if start is bigger than end, it's an invalid range
-    if start > end {
-        return Err(RangeError::StartBiggerThanEnd(part.to_string()));
-    }
+/// Parse a range string to a vector of any kind of number, enforcing the provided [`Limits`]
+///
+/// The limits are checked before allocating any value: [`RangeError::TooManySegments`] is returned if the range
+/// string has too many segments, and [`RangeError::TooLarge`] if the range would produce too many values.
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+/// - limits: &Limits - the limits to enforce
+///
+/// # Returns
+/// - Result<Vec<T>, RangeError> - the parsed range
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::{Limits, RangeError};
+///
+/// let limits = Limits::new().max_items(100);
+/// assert_eq!(range_parser::parse_limited::<u32>("1-3,7", &limits).unwrap(), vec![1, 2, 3, 7]);
+/// assert_eq!(
+///     range_parser::parse_limited::<u32>("0-4000000000", &limits).unwrap_err(),
+///     RangeError::TooLarge(4000000001)
+/// );
+/// ```
+pub fn parse_limited<T>(range_str: &str, limits: &Limits) -> RangeResult<Vec<T>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    parse_limited_with(range_str, ",", "-", limits)
+}
 
-    let mut x = start;
-    while x <= end {
-        acc.push(x);
-        x = x + T::unit();
-    }
+/// Parse a range string to a vector of any kind of number with custom separators, enforcing the provided [`Limits`]
+///
+/// See [`parse_limited`] for how limits are enforced.
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+/// - value_separator: &str - the separator for single values
+/// - range_separator: &str - the separator for ranges
+/// - limits: &Limits - the limits to enforce
+///
+/// # Returns
+/// - Result<Vec<T>, RangeError> - the parsed range
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::{Limits, RangeError};
+///
+/// let limits = Limits::new().max_segments(2);
+/// assert_eq!(
+///     range_parser::parse_limited_with::<u32>("1;2;3", ";", "..", &limits).unwrap_err(),
+///     RangeError::TooManySegments(3)
+/// );
+/// ```
+pub fn parse_limited_with<T>(
+    range_str: &str,
+    value_separator: &str,
+    range_separator: &str,
+    limits: &Limits,
+) -> RangeResult<Vec<T>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    RangeParser::with_separators(value_separator, range_separator)
+        .limits(*limits)
+        .parse(range_str)
+}
 
-    Ok(())
+/// Parse a range string to a lazy iterator over any kind of number
+///
+/// The whole range string is validated before returning, but values are only produced while iterating,
+/// so no memory is allocated for the items of the range.
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+///
+/// # Returns
+/// - Result<RangeIter<T>, RangeError> - the iterator over the parsed range
+///
+/// # Example
+///
+/// ```rust
+/// let mut iter = range_parser::parse_iter::<u64>("0-3,8").unwrap();
+/// assert_eq!(iter.size_hint(), (5, Some(5)));
+/// assert_eq!(iter.next(), Some(0));
+/// assert_eq!(iter.next_back(), Some(8));
+/// ```
+pub fn parse_iter<T>(range_str: &str) -> RangeResult<RangeIter<T>>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    parse_iter_with(range_str, ",", "-")
 }
 
-/// Parse a string to a T
-fn parse_as_t<T>(part: &str) -> RangeResult<T>
+/// Parse a range string to a lazy iterator over any kind of number with custom separators
+///
+/// See [`parse_with`] for the rules about separators.
+///
+/// # Arguments
+/// - range_str: &str - the range string to parse
+/// - value_separator: &str - the separator for single values
+/// - range_separator: &str - the separator for ranges
+///
+/// # Returns
+/// - Result<RangeIter<T>, RangeError> - the iterator over the parsed range
+///
+/// # Example
+///
+/// ```rust
+/// let iter = range_parser::parse_iter_with::<i32>("0;3;5..8;-1", ";", "..").unwrap();
+/// assert_eq!(iter.rev().collect::<Vec<i32>>(), vec![-1, 8, 7, 6, 5, 3, 0]);
+/// ```
+pub fn parse_iter_with<T>(
+    range_str: &str,
+    value_separator: &str,
+    range_separator: &str,
+) -> RangeResult<RangeIter<T>>
 where
-    T: FromStr + Add<Output = T> + PartialEq + PartialOrd + Unit + Copy,
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
 {
-    part.trim()
-        .parse()
-        .map_err(|_| RangeError::NotANumber(part.to_string()))
+    let selections = RangeParser::with_separators(value_separator, range_separator)
+        .selections(range_str, None)?;
+    check_countable(selections.iter().filter_map(|selection| match selection {
+        Selection::Include(segment) => Some(segment),
+        Selection::Exclude(_) => None,
+    }))?;
+
+    Ok(RangeIter::from_selections(selections))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::num::{NonZeroI32, NonZeroI8, NonZeroU32, NonZeroU64, NonZeroU8};
+
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -345,4 +642,777 @@ mod tests {
     fn test_should_not_allow_ambiguous_separator() {
         assert!(parse_with::<i32>("1--3", "-", "--").is_err());
     }
+
+    macro_rules! test_range_ending_at_max {
+        ($($name:ident: $t:ty),*) => ($(
+            #[test]
+            fn $name() {
+                let range_str = format!("{}-{},{}", <$t>::MAX - 2, <$t>::MAX, <$t>::MAX);
+                let expected = vec![<$t>::MAX - 2, <$t>::MAX - 1, <$t>::MAX, <$t>::MAX];
+                assert_eq!(parse::<$t>(&range_str).unwrap(), expected);
+                assert_eq!(parse_iter::<$t>(&range_str).unwrap().collect::<Vec<$t>>(), expected);
+            }
+        )*)
+    }
+
+    test_range_ending_at_max!(
+        should_parse_range_ending_at_usize_max: usize,
+        should_parse_range_ending_at_u8_max: u8,
+        should_parse_range_ending_at_u16_max: u16,
+        should_parse_range_ending_at_u32_max: u32,
+        should_parse_range_ending_at_u64_max: u64,
+        should_parse_range_ending_at_isize_max: isize,
+        should_parse_range_ending_at_i8_max: i8,
+        should_parse_range_ending_at_i16_max: i16,
+        should_parse_range_ending_at_i32_max: i32,
+        should_parse_range_ending_at_i64_max: i64,
+        should_parse_range_ending_at_u128_max: u128,
+        should_parse_range_ending_at_i128_max: i128
+    );
+
+    #[test]
+    fn should_parse_128_bit_ranges() {
+        let range: Vec<i128> = parse(
+            "-170141183460469231731687303715884105728--170141183460469231731687303715884105727",
+        )
+        .unwrap();
+        assert_eq!(range, vec![i128::MIN, i128::MIN + 1]);
+        let range: Vec<u128> = parse("18446744073709551615-18446744073709551617").unwrap();
+        assert_eq!(
+            range,
+            vec![u64::MAX as u128, u64::MAX as u128 + 1, u64::MAX as u128 + 2]
+        );
+        // the whole domain is too large to be expanded, but it's a valid range
+        let domain = "0-340282366920938463463374607431768211455";
+        assert_eq!(
+            parse::<u128>(domain).unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+        assert_eq!(
+            parse_iter::<u128>(domain).unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+        assert_eq!(
+            parse_iter::<u128>("0-100000000000000000000000:2").unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+        assert_eq!(
+            RangeSet::<u128>::parse(domain).unwrap().intervals(),
+            &[0..=u128::MAX]
+        );
+        assert_eq!(
+            RangeSet::<i128>::parse("-5-170141183460469231731687303715884105727,!0")
+                .unwrap()
+                .intervals(),
+            &[-5..=-1, 1..=i128::MAX]
+        );
+        assert_eq!(
+            RangeParser::rust()
+                .parse_ranges::<u128>("0..340282366920938463463374607431768211455")
+                .unwrap(),
+            vec![0..u128::MAX]
+        );
+        assert_eq!(
+            RangeSet::<u128>::parse("0-100000000000000000000000:2").unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn should_parse_non_zero_ranges() {
+        let range: Vec<NonZeroU64> = parse("1-3,7").unwrap();
+        assert_eq!(
+            range.iter().map(|value| value.get()).collect::<Vec<u64>>(),
+            vec![1, 2, 3, 7]
+        );
+        let range: Vec<NonZeroU8> = parse("254-255").unwrap();
+        assert_eq!(range, vec![NonZeroU8::new(254).unwrap(), NonZeroU8::MAX]);
+        let range: Vec<NonZeroU32> = parse_bounded("-2", NonZeroU32::MIN, NonZeroU32::MAX).unwrap();
+        assert_eq!(range, vec![NonZeroU32::MIN, NonZeroU32::new(2).unwrap()]);
+        let range: Vec<NonZeroU8> = parse("1-7:3").unwrap();
+        assert_eq!(
+            range.iter().map(|value| value.get()).collect::<Vec<u8>>(),
+            vec![1, 4, 7]
+        );
+    }
+
+    #[test]
+    fn should_skip_zero_in_signed_non_zero_ranges() {
+        let range: Vec<NonZeroI32> = parse("-1-1").unwrap();
+        assert_eq!(
+            range.iter().map(|value| value.get()).collect::<Vec<i32>>(),
+            vec![-1, 1]
+        );
+        let range: Vec<NonZeroI32> = parse("-4-4:2").unwrap();
+        assert_eq!(
+            range.iter().map(|value| value.get()).collect::<Vec<i32>>(),
+            vec![-4, -2, 2, 4]
+        );
+        let range: Vec<NonZeroI8> = parse("-128-127").unwrap();
+        assert_eq!(range.len(), 255);
+        assert_eq!(range.first(), Some(&NonZeroI8::MIN));
+        assert_eq!(range.last(), Some(&NonZeroI8::MAX));
+        assert!(range.iter().all(|value| value.get() != 0));
+        assert_eq!(
+            parse_iter::<NonZeroI8>("-128-127").unwrap().rev().nth(127),
+            NonZeroI8::new(-1)
+        );
+        assert_eq!(
+            RangeSet::<NonZeroI8>::parse("-128-127,!-1")
+                .unwrap()
+                .intervals(),
+            &[
+                NonZeroI8::MIN..=NonZeroI8::new(-2).unwrap(),
+                NonZeroI8::new(1).unwrap()..=NonZeroI8::MAX
+            ]
+        );
+        assert_eq!(
+            parse::<NonZeroI32>("-1,0-3").unwrap_err(),
+            RangeError::ZeroValue(Fragment::new("0", 3..4, 1))
+        );
+        assert_eq!(
+            parse::<NonZeroI32>("1-5:-1").unwrap_err(),
+            RangeError::NegativeStep(Fragment::new("1-5:-1", 0..6, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_parse_zero_for_non_zero_types() {
+        assert_eq!(
+            parse::<NonZeroU64>("1,0-3").unwrap_err(),
+            RangeError::ZeroValue(Fragment::new("0", 2..3, 1))
+        );
+        assert_eq!(
+            parse::<NonZeroU32>("0").unwrap_err(),
+            RangeError::ZeroValue(Fragment::new("0", 0..1, 0))
+        );
+        assert_eq!(
+            parse::<NonZeroU8>("3,0-5").unwrap_err(),
+            RangeError::ZeroValue(Fragment::new("0", 2..3, 1))
+        );
+        assert_eq!(
+            parse_iter::<NonZeroU32>("0-5").unwrap_err(),
+            RangeError::ZeroValue(Fragment::new("0", 0..1, 0))
+        );
+        assert_eq!(
+            parse::<NonZeroU32>("x").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 0..1, 0))
+        );
+        assert_eq!(
+            parse::<u32>("1-2:0").unwrap_err(),
+            RangeError::ZeroStep(Fragment::new("1-2:0", 0..5, 0))
+        );
+    }
+
+    #[test]
+    fn should_parse_range_starting_at_signed_min() {
+        let range: Vec<i8> = parse("-128--126").unwrap();
+        assert_eq!(range, vec![-128, -127, -126]);
+    }
+
+    #[test]
+    fn should_parse_whole_u8_domain() {
+        let range: Vec<u8> = parse("0-255").unwrap();
+        assert_eq!(range.len(), 256);
+        assert_eq!(range.last(), Some(&u8::MAX));
+    }
+
+    #[test]
+    fn should_stop_when_float_cannot_be_incremented() {
+        // 16777216 + 1 is not representable as f32
+        let range: Vec<f32> = parse("16777215-16777218").unwrap();
+        assert_eq!(range, vec![16777215.0, 16777216.0]);
+    }
+
+    #[test]
+    fn should_parse_range_with_step() {
+        let range: Vec<u32> = parse("0-100:25,7").unwrap();
+        assert_eq!(range, vec![0, 25, 50, 75, 100, 7]);
+        let range: Vec<i32> = parse("-10--1:3").unwrap();
+        assert_eq!(range, vec![-10, -7, -4, -1]);
+    }
+
+    #[test]
+    fn should_not_include_unreachable_end_with_step() {
+        let range: Vec<u8> = parse("0-10:4").unwrap();
+        assert_eq!(range, vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn should_parse_range_with_fractional_step() {
+        let range: Vec<f64> = parse("0-1:0.25").unwrap();
+        assert_eq!(range, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        let range: Vec<f32> = parse("-1-1:0.5").unwrap();
+        assert_eq!(range, vec![-1.0, -0.5, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn should_not_accumulate_float_errors_with_step() {
+        let range: Vec<f64> = parse("0-1:0.1").unwrap();
+        assert_eq!(range.len(), 11);
+        assert_eq!(range.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn should_not_round_float_steps_beyond_end_of_large_bounds() {
+        for range_str in [
+            "1000000000000000-1000000000000010.625:1",
+            "1000000000000000-1000000000000010.625",
+        ] {
+            let range: Vec<f64> = parse(range_str).unwrap();
+            assert_eq!(range.len(), 11);
+            assert_eq!(range.last(), Some(&1000000000000010.0));
+            let range: Vec<f64> = parse_iter(range_str).unwrap().collect();
+            assert_eq!(range.len(), 11);
+            assert_eq!(range.last(), Some(&1000000000000010.0));
+        }
+    }
+
+    #[test]
+    fn should_include_float_end_reached_by_step() {
+        assert_eq!(parse::<f64>("0.1-0.3:0.1").unwrap(), vec![0.1, 0.2, 0.3]);
+        assert_eq!(parse::<f64>("0-0.3:0.1").unwrap(), vec![0.0, 0.1, 0.2, 0.3]);
+        let range: Vec<f64> = parse("0-0.7:0.1").unwrap();
+        assert_eq!(range.len(), 8);
+        assert_eq!(range.last(), Some(&0.7));
+        assert_eq!(
+            RangeParser::rust().parse::<f64>("0..=0.3:0.1").unwrap(),
+            vec![0.0, 0.1, 0.2, 0.3]
+        );
+        assert_eq!(
+            RangeParser::rust().parse::<f64>("0..0.3:0.1").unwrap(),
+            vec![0.0, 0.1, 0.2]
+        );
+        assert_eq!(
+            parse_iter::<f64>("0-0.3:0.1")
+                .unwrap()
+                .collect::<Vec<f64>>(),
+            vec![0.0, 0.1, 0.2, 0.3]
+        );
+        assert_eq!(
+            parse_iter::<f64>("0.1-0.3:0.1")
+                .unwrap()
+                .rev()
+                .collect::<Vec<f64>>(),
+            vec![0.3, 0.2, 0.1]
+        );
+        assert_eq!(parse::<f64>("0-0.35:0.1").unwrap().len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn should_parse_range_of_decimals_exactly() {
+        let range: Vec<rust_decimal::Decimal> = parse("0.0-1.0:0.1").unwrap();
+        assert_eq!(
+            range.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+            vec!["0.0", "0.1", "0.2", "0.3", "0.4", "0.5", "0.6", "0.7", "0.8", "0.9", "1.0"]
+        );
+    }
+
+    #[test]
+    fn should_stop_step_at_type_maximum() {
+        let range: Vec<u8> = parse("240-255:10").unwrap();
+        assert_eq!(range, vec![240, 250]);
+        let range: Vec<u8> = parse("250-255:100").unwrap();
+        assert_eq!(range, vec![250]);
+    }
+
+    #[test]
+    fn should_parse_range_with_custom_step_separator() {
+        let range: Vec<i32> = parse_with_separators("-4..4/4;9", ";", "..", "/").unwrap();
+        assert_eq!(range, vec![-4, 0, 4, 9]);
+    }
+
+    #[test]
+    fn should_not_allow_zero_step() {
+        assert_eq!(
+            parse::<u32>("0-10:0").unwrap_err(),
+            RangeError::ZeroStep(Fragment::new("0-10:0", 0..6, 0))
+        );
+        assert_eq!(
+            parse::<f64>("0-1:0.0").unwrap_err(),
+            RangeError::ZeroStep(Fragment::new("0-1:0.0", 0..7, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_allow_negative_step() {
+        assert_eq!(
+            parse::<i32>("0-10:-2").unwrap_err(),
+            RangeError::NegativeStep(Fragment::new("0-10:-2", 0..7, 0))
+        );
+        assert_eq!(
+            parse::<f32>("0-1:-0.5").unwrap_err(),
+            RangeError::NegativeStep(Fragment::new("0-1:-0.5", 0..8, 0))
+        );
+        assert!(parse::<u32>("0-10:-2").is_err());
+    }
+
+    #[test]
+    fn should_not_allow_step_without_range() {
+        assert!(parse::<u32>("5:2").is_err());
+        assert!(parse::<u32>("0-10:x").is_err());
+    }
+
+    #[test]
+    fn should_disable_steps_clashing_with_custom_separators() {
+        assert_eq!(parse_with::<u32>("1:2:3", ":", "-").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_with::<u32>("1:3", ",", ":").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            parse_bounded_with::<u32>("1:3,5:", 0, 6, ",", ":").unwrap(),
+            vec![1, 2, 3, 5, 6]
+        );
+        assert_eq!(
+            parse_iter_with::<u32>("7:0-2", ":", "-")
+                .unwrap()
+                .collect::<Vec<u32>>(),
+            vec![7, 0, 1, 2]
+        );
+        assert_eq!(parse_with::<u32>("0-4:2", ",", "-").unwrap(), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn should_disable_exclusions_clashing_with_custom_separators() {
+        assert_eq!(parse_with::<u32>("1!2", "!", "-").unwrap(), vec![1, 2]);
+        assert_eq!(parse_with::<u32>("1!3", ",", "!").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            parse_with::<u32>("1-5,!3", ",", "-").unwrap(),
+            vec![1, 2, 4, 5]
+        );
+    }
+
+    #[test]
+    fn should_disable_point_counts_clashing_with_custom_separators() {
+        assert_eq!(parse_with::<u32>("1#2", "#", "-").unwrap(), vec![1, 2]);
+        assert_eq!(parse_with::<u32>("1#3", ",", "#").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_with::<u32>("0-4#3", ",", "-").unwrap(), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn should_disable_factors_clashing_with_custom_separators() {
+        assert_eq!(parse_with::<u32>("1*2", "*", "-").unwrap(), vec![1, 2]);
+        assert_eq!(parse_with::<u32>("1*3", ",", "*").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            parse_with::<u32>("1-100*10", ",", "-").unwrap(),
+            vec![1, 10, 100]
+        );
+    }
+
+    #[test]
+    fn should_not_allow_same_step_separator() {
+        assert_eq!(
+            parse_with_separators::<u32>("0-10", ",", "-", "-").unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+    }
+
+    #[test]
+    fn should_parse_range_with_point_count() {
+        let range: Vec<f64> = parse("0-1#5").unwrap();
+        assert_eq!(range, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+        let range: Vec<u32> = parse("0-100#5,7").unwrap();
+        assert_eq!(range, vec![0, 25, 50, 75, 100, 7]);
+        let range: Vec<f32> = parse("0-1#100").unwrap();
+        assert_eq!(range.len(), 100);
+    }
+
+    #[test]
+    fn should_not_parse_uneven_point_count_for_integers() {
+        assert_eq!(
+            parse::<u32>("0-10#4").unwrap_err(),
+            RangeError::UnevenPoints(Fragment::new("0-10#4", 0..6, 0))
+        );
+        assert!(parse::<char>("a-z#2").is_err());
+    }
+
+    #[test]
+    fn should_not_parse_invalid_point_count() {
+        assert!(parse::<f64>("0-1#1").is_err());
+        assert!(parse::<f64>("0-1#0").is_err());
+        assert!(parse::<f64>("0-1#x").is_err());
+        assert!(parse::<f64>("5#3").is_err());
+        assert!(parse::<f64>("0-1#3:0.5").is_err());
+        assert!(parse::<f64>("1-1#3").is_err());
+    }
+
+    #[test]
+    fn should_parse_geometric_range() {
+        let range: Vec<u32> = parse("1-1000*10").unwrap();
+        assert_eq!(range, vec![1, 10, 100, 1000]);
+        let range: Vec<u8> = parse("3-255*2,1").unwrap();
+        assert_eq!(range, vec![3, 6, 12, 24, 48, 96, 192, 1]);
+        let range: Vec<f64> = parse("0.5-8*2").unwrap();
+        assert_eq!(range, vec![0.5, 1.0, 2.0, 4.0, 8.0]);
+        let range: Vec<u64> = parse("1-1000*10,!10-100").unwrap();
+        assert_eq!(range, vec![1, 1000]);
+    }
+
+    #[test]
+    fn should_parse_log_spaced_range() {
+        let range: Vec<f64> = parse("1-1000*#4").unwrap();
+        assert_eq!(range.len(), 4);
+        assert_eq!(range[0], 1.0);
+        assert!((range[2] - 100.0).abs() < 1e-9);
+        assert!((range[3] - 1000.0).abs() < 1e-9);
+        let range: Vec<u32> = parse("2-162*#5").unwrap();
+        assert_eq!(range, vec![2, 6, 18, 54, 162]);
+        assert_eq!(
+            parse::<u32>("1-100*#4").unwrap_err(),
+            RangeError::UnevenPoints(Fragment::new("1-100*#4", 0..8, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_parse_invalid_geometric_range() {
+        assert_eq!(
+            parse::<f64>("1-10*1").unwrap_err(),
+            RangeError::InvalidFactor(Fragment::new("1-10*1", 0..6, 0))
+        );
+        assert_eq!(
+            parse::<f64>("1-10*0.5").unwrap_err(),
+            RangeError::InvalidFactor(Fragment::new("1-10*0.5", 0..8, 0))
+        );
+        assert_eq!(
+            parse::<i32>("0-100*2").unwrap_err(),
+            RangeError::UnreachableEnd(Fragment::new("0-100*2", 0..7, 0))
+        );
+        assert_eq!(
+            parse::<i32>("-8--1*2").unwrap_err(),
+            RangeError::UnreachableEnd(Fragment::new("-8--1*2", 0..7, 0))
+        );
+        assert!(parse::<u32>("5*2").is_err());
+        assert!(parse::<u32>("1-100*2:2").is_err());
+        assert!(parse::<u32>("1-100*2#3").is_err());
+        assert!(parse::<u32>("1-100*").is_err());
+        assert_eq!(
+            parse::<char>("b-z*c").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("b-z*c", 0..5, 0))
+        );
+    }
+
+    #[test]
+    fn should_parse_geometric_range_within_bounds() {
+        let range: Vec<u32> = parse_bounded("**4", 1, 100).unwrap();
+        assert_eq!(range, vec![1, 4, 16, 64]);
+        let range: Vec<u32> = parse_bounded("*", 1, 3).unwrap();
+        assert_eq!(range, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn should_parse_range_with_step_lazily() {
+        let iter = parse_iter::<u32>("0-10:3").unwrap();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        assert_eq!(iter.rev().collect::<Vec<u32>>(), vec![9, 6, 3, 0]);
+    }
+
+    #[test]
+    fn should_parse_open_ended_ranges() {
+        let range: Vec<usize> = parse_bounded("3-", 1, 5).unwrap();
+        assert_eq!(range, vec![3, 4, 5]);
+        let range: Vec<usize> = parse_bounded("-3", 1, 5).unwrap();
+        assert_eq!(range, vec![1, 2, 3]);
+        let range: Vec<usize> = parse_bounded("*", 1, 5).unwrap();
+        assert_eq!(range, vec![1, 2, 3, 4, 5]);
+        let range: Vec<usize> = parse_bounded("-2,4,5-", 1, 6).unwrap();
+        assert_eq!(range, vec![1, 2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn should_parse_open_ended_ranges_with_negative_numbers() {
+        let range: Vec<i32> = parse_bounded("-3", -5, 5).unwrap();
+        assert_eq!(range, vec![-3]);
+        let range: Vec<i32> = parse_bounded("-2-,-5--4", -5, 0).unwrap();
+        assert_eq!(range, vec![-2, -1, 0, -5, -4]);
+    }
+
+    #[test]
+    fn should_parse_open_ended_ranges_with_custom_separators() {
+        let range: Vec<i32> = parse_bounded_with("..-8;0;5..", -10, 7, ";", "..").unwrap();
+        assert_eq!(range, vec![-10, -9, -8, 0, 5, 6, 7]);
+        let range: Vec<u8> = parse_bounded_with("..2;*", 0, 3, ";", "..").unwrap();
+        assert_eq!(range, vec![0, 1, 2, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn should_parse_open_ended_ranges_with_step() {
+        let range: Vec<u32> = parse_bounded("*:4,10-:5", 0, 20).unwrap();
+        assert_eq!(range, vec![0, 4, 8, 12, 16, 20, 10, 15, 20]);
+    }
+
+    #[test]
+    fn should_not_allow_values_out_of_bounds() {
+        assert_eq!(
+            parse_bounded::<u32>("1,7", 1, 5).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("7", 2..3, 1))
+        );
+        assert_eq!(
+            parse_bounded::<u32>("0-", 1, 5).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("0-", 0..2, 0))
+        );
+        assert_eq!(
+            parse_bounded::<u32>("-9", 1, 5).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("-9", 0..2, 0))
+        );
+    }
+
+    #[test]
+    fn should_not_allow_open_ranges_without_bounds() {
+        assert!(parse::<u32>("3-").is_err());
+        assert!(parse::<u32>("*").is_err());
+        assert!(parse::<u32>("-3").is_err());
+    }
+
+    #[test]
+    fn should_locate_errors_in_input() {
+        let err = parse::<u32>("1,3-x,5").unwrap_err();
+        assert_eq!(err, RangeError::NotANumber(Fragment::new("x", 4..5, 1)));
+        assert_eq!(err.render("1,3-x,5"), "1,3-x,5\n    ^");
+
+        let err = parse_with::<i32>("1;;2", ";", "..").unwrap_err();
+        assert_eq!(err.span(), Some(2..2));
+        assert_eq!(err.segment(), Some(1));
+
+        let err = parse_with::<i32>("0 ;  9..2", ";", "..").unwrap_err();
+        assert_eq!(
+            err,
+            RangeError::StartBiggerThanEnd(Fragment::new("  9..2", 5..9, 1))
+        );
+    }
+
+    #[test]
+    fn should_locate_errors_with_negative_numbers() {
+        let err = parse::<i32>("0,-5--x").unwrap_err();
+        assert_eq!(err.span(), Some(5..7));
+        assert_eq!(err.segment(), Some(1));
+    }
+
+    #[test]
+    fn should_locate_errors_on_repeated_tokens() {
+        assert_eq!(
+            parse::<i32>("1-3:1-").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("1-", 4..6, 0))
+        );
+        assert_eq!(
+            parse::<i32>("0-10:-").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("-", 5..6, 0))
+        );
+        assert_eq!(
+            parse::<u32>("2,2-2x").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("2x", 4..6, 1))
+        );
+        let err = parse::<u32>("7,!7-7:x7").unwrap_err();
+        assert_eq!(err, RangeError::NotANumber(Fragment::new("x7", 7..9, 1)));
+        assert_eq!(err.render("7,!7-7:x7"), "7,!7-7:x7\n       ^^");
+    }
+
+    #[test]
+    fn should_parse_within_limits() {
+        let limits = Limits::new().max_items(5).max_segments(2);
+        assert_eq!(
+            parse_limited::<u8>("1-3,9", &limits).unwrap(),
+            vec![1, 2, 3, 9]
+        );
+        assert_eq!(
+            parse_limited::<u8>("0-100:25", &limits).unwrap(),
+            vec![0, 25, 50, 75, 100]
+        );
+    }
+
+    #[test]
+    fn should_not_parse_beyond_limits() {
+        let limits = Limits::new().max_items(5).max_segments(2);
+        assert_eq!(
+            parse_limited::<u8>("1-3,9-11", &limits).unwrap_err(),
+            RangeError::TooLarge(6)
+        );
+        assert_eq!(
+            parse_limited::<u8>("1,2,3", &limits).unwrap_err(),
+            RangeError::TooManySegments(3)
+        );
+        assert_eq!(
+            parse_limited::<u64>("0-18446744073709551615", &limits).unwrap_err(),
+            RangeError::TooLarge(18446744073709551616)
+        );
+        // the steps of this range can't be counted, but it's still too large
+        let limits = Limits::new().max_items(10);
+        assert_eq!(
+            parse_limited::<u128>("0-100000000000000000000000", &limits).unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+        assert_eq!(
+            parse_limited::<u128>("0-100000000000000000000000:2", &limits).unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+        assert_eq!(
+            parse::<u128>("0-100000000000000000000000").unwrap_err(),
+            RangeError::TooLarge(u128::MAX)
+        );
+    }
+
+    #[test]
+    fn should_check_syntax_before_limits() {
+        let limits = Limits::new().max_items(5);
+        assert!(matches!(
+            parse_limited::<u8>("1-x", &limits).unwrap_err(),
+            RangeError::NotANumber(_)
+        ));
+    }
+
+    #[test]
+    fn should_parse_float_exponents() {
+        let range: Vec<f64> = parse("1e-3-2e-3,1E+1-1.2e1").unwrap();
+        assert_eq!(range, vec![1e-3, 10.0, 11.0, 12.0]);
+        let range: Vec<f64> = parse("-1e1--8e0").unwrap();
+        assert_eq!(range, vec![-10.0, -9.0, -8.0]);
+        let range: Vec<f64> = parse_with_separators("0..1e-2:2.5e-3", ",", "..", ":").unwrap();
+        assert_eq!(range, vec![0.0, 0.0025, 0.005, 0.0075, 0.01]);
+    }
+
+    #[test]
+    fn should_parse_explicit_signs() {
+        let range: Vec<i32> = parse("+1-+3,-2-+1").unwrap();
+        assert_eq!(range, vec![1, 2, 3, -2, -1, 0, 1]);
+        let range: Vec<u8> = parse("+1 - +3").unwrap();
+        assert_eq!(range, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn should_parse_ranges_with_word_separator() {
+        let range: Vec<i32> = parse_with("-3 to -1; 2", ";", "to").unwrap();
+        assert_eq!(range, vec![-3, -2, -1, 2]);
+    }
+
+    #[test]
+    fn should_not_parse_trailing_values() {
+        assert_eq!(
+            parse::<i32>("0,1-2-3").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("1-2-3", 2..7, 1))
+        );
+        assert_eq!(
+            parse::<i32>("1 2").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("1 2", 0..3, 0))
+        );
+        assert_eq!(
+            parse::<i32>("1 2-3").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("1 2-3", 0..5, 0))
+        );
+    }
+
+    #[test]
+    fn should_parse_char_ranges() {
+        let range: Vec<char> = parse("a-e").unwrap();
+        assert_eq!(range, vec!['a', 'b', 'c', 'd', 'e']);
+        let range: Vec<char> = parse("A-F,0-9").unwrap();
+        assert_eq!(range.iter().collect::<String>(), "ABCDEF0123456789");
+        let range: Vec<char> = parse("x-z,!y,_").unwrap();
+        assert_eq!(range, vec!['x', 'z', '_']);
+        let range: Vec<char> = parse("\u{D7FE}-\u{E001}").unwrap();
+        assert_eq!(range, vec!['\u{D7FE}', '\u{D7FF}', '\u{E000}', '\u{E001}']);
+        let iter = parse_iter::<char>("\0-\u{10FFFF}").unwrap();
+        assert_eq!(iter.size_hint(), (0x110000 - 0x800, Some(0x110000 - 0x800)));
+    }
+
+    #[test]
+    fn should_not_parse_mixed_char_segments() {
+        assert_eq!(
+            parse::<char>("a-c,a-10").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("10", 6..8, 1))
+        );
+        assert_eq!(
+            parse::<char>("ab-z").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("ab", 0..2, 0))
+        );
+        assert_eq!(
+            parse::<char>("z-a").unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("z-a", 0..3, 0))
+        );
+        assert_eq!(
+            parse::<char>("a-z:2").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("a-z:2", 0..5, 0))
+        );
+    }
+
+    #[test]
+    fn should_exclude_values() {
+        let range: Vec<u32> = parse("1-20,!5,!10-12").unwrap();
+        assert_eq!(
+            range,
+            vec![1, 2, 3, 4, 6, 7, 8, 9, 13, 14, 15, 16, 17, 18, 19, 20]
+        );
+        let range: Vec<i32> = parse("-5-5,!-3--1,!0-4:2").unwrap();
+        assert_eq!(range, vec![-5, -4, 1, 3, 5]);
+    }
+
+    #[test]
+    fn should_exclude_values_in_order() {
+        // exclusions only remove the values accumulated so far
+        let range: Vec<u32> = parse("!2,1-3").unwrap();
+        assert_eq!(range, vec![1, 2, 3]);
+        let range: Vec<u32> = parse("1-5,!2-4,3").unwrap();
+        assert_eq!(range, vec![1, 5, 3]);
+        let range: Vec<u32> = parse("2,1-3,!2").unwrap();
+        assert_eq!(range, vec![1, 3]);
+    }
+
+    #[test]
+    fn should_exclude_values_within_bounds() {
+        let range: Vec<u8> = parse_bounded("*,!3-", 1, 8).unwrap();
+        assert_eq!(range, vec![1, 2]);
+        assert_eq!(
+            parse_bounded::<u8>("*,!9", 1, 8).unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("9", 3..4, 1))
+        );
+    }
+
+    #[test]
+    fn should_locate_errors_in_exclusions() {
+        assert_eq!(
+            parse::<u32>("1-9,!x").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 5..6, 1))
+        );
+        assert_eq!(
+            parse::<u32>("1-9,!").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("", 4..5, 1))
+        );
+    }
+
+    #[test]
+    fn should_exclude_values_lazily() {
+        let mut iter = parse_iter::<u64>("0-4000000000,!1-3999,!4000000000").unwrap();
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(4000));
+        assert_eq!(iter.next_back(), Some(3999999999));
+    }
+
+    #[test]
+    fn should_parse_range_lazily() {
+        let iter = parse_iter::<i32>("-2,0-3,-1,7").unwrap();
+        assert_eq!(iter.collect::<Vec<i32>>(), vec![-2, 0, 1, 2, 3, -1, 7]);
+    }
+
+    #[test]
+    fn should_parse_range_lazily_with_custom_separators() {
+        let iter = parse_iter_with::<i32>("-2;0..3;-1;7", ";", "..").unwrap();
+        assert_eq!(
+            iter.rev().collect::<Vec<i32>>(),
+            vec![7, -1, 3, 2, 1, 0, -2]
+        );
+    }
+
+    #[test]
+    fn should_validate_whole_range_before_iterating() {
+        assert_eq!(
+            parse_iter::<u32>("0-4000000000,5-x").unwrap_err(),
+            RangeError::NotANumber(Fragment::new("x", 15..16, 1))
+        );
+        assert_eq!(
+            parse_iter::<u32>("0-4000000000,5-1").unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("5-1", 13..16, 1))
+        );
+    }
+
+    #[test]
+    fn should_not_allow_uncountable_range() {
+        assert!(parse_iter::<f64>("0-1e300").is_err());
+    }
 }
@@ -0,0 +1,166 @@
+use crate::segment::Selection;
+use crate::{RangeError, RangeResult, Unit};
+
+/// Limits to the size of a parsed range, to protect from hostile input
+///
+/// By default there are no limits.
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::{Limits, RangeError};
+///
+/// let limits = Limits::new().max_items(1_000).max_segments(16);
+/// assert_eq!(
+///     range_parser::parse_limited::<u64>("0-18446744073709551615", &limits).unwrap_err(),
+///     RangeError::TooLarge(18446744073709551616)
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    max_items: Option<usize>,
+    max_segments: Option<usize>,
+}
+
+impl Limits {
+    /// Create new [`Limits`], without any limit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum amount of values the range can produce
+    ///
+    /// Only the values of included segments are counted: excluded segments (e.g. `!0-4000000000:2`) are never
+    /// expanded, since they only remove values which have been included, so they can't make the range bigger.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Set the maximum amount of segments, as delimited by the value separator, the range string can contain
+    pub fn max_segments(mut self, max_segments: usize) -> Self {
+        self.max_segments = Some(max_segments);
+        self
+    }
+
+    /// Check the amount of segments of the range string, before parsing them
+    pub(crate) fn check_segments(&self, range_str: &str, value_separator: &str) -> RangeResult<()> {
+        let Some(max_segments) = self.max_segments else {
+            return Ok(());
+        };
+        let segments = range_str.split(value_separator).count();
+        if segments > max_segments {
+            return Err(RangeError::TooManySegments(segments));
+        }
+        Ok(())
+    }
+
+    /// Check the amount of values the included segments would produce, before expanding them
+    ///
+    /// Exclusions aren't counted, because their values are either checked by membership or produced only within
+    /// the included values.
+    pub(crate) fn check_items<T>(&self, selections: &[Selection<T>]) -> RangeResult<()>
+    where
+        T: Unit,
+    {
+        let Some(max_items) = self.max_items else {
+            return Ok(());
+        };
+        let items = selections
+            .iter()
+            .fold(0u128, |acc, selection| match selection {
+                Selection::Include(segment) => acc.saturating_add(segment.count()),
+                Selection::Exclude(_) => acc,
+            });
+        if items > max_items as u128 {
+            return Err(RangeError::TooLarge(items));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::segment::Segment;
+
+    #[test]
+    fn should_not_limit_by_default() {
+        let limits = Limits::default();
+        assert!(limits.check_segments("1,2,3", ",").is_ok());
+        assert!(limits
+            .check_items(&[
+                Selection::Include(Segment::Range(0u64, u64::MAX)),
+                Selection::Include(Segment::Value(1))
+            ])
+            .is_ok());
+    }
+
+    #[test]
+    fn should_limit_segments() {
+        let limits = Limits::new().max_segments(2);
+        assert!(limits.check_segments("1,2", ",").is_ok());
+        assert_eq!(
+            limits.check_segments("1,2,3", ","),
+            Err(RangeError::TooManySegments(3))
+        );
+    }
+
+    #[test]
+    fn should_limit_items() {
+        let limits = Limits::new().max_items(10);
+        assert!(limits
+            .check_items(&[
+                Selection::Include(Segment::Range(1u32, 9)),
+                Selection::Include(Segment::Value(20))
+            ])
+            .is_ok());
+        assert_eq!(
+            limits.check_items(&[
+                Selection::Include(Segment::Range(1u32, 10)),
+                Selection::Include(Segment::Value(20))
+            ]),
+            Err(RangeError::TooLarge(11))
+        );
+        assert_eq!(
+            limits.check_items(&[Selection::Include(Segment::SteppedRange(0u32, 100, 10))]),
+            Err(RangeError::TooLarge(11))
+        );
+    }
+
+    #[test]
+    fn should_not_expand_excluded_items() {
+        // expanding the excluded values would allocate billions of them
+        let limits = Limits::new().max_items(10);
+        let excluded = "0-9,!0-4294967295:2";
+        assert_eq!(
+            crate::parse_limited::<u32>(excluded, &limits).unwrap(),
+            vec![1, 3, 5, 7, 9]
+        );
+        assert_eq!(
+            crate::RangeSet::<u32>::parse_limited(excluded, &limits)
+                .unwrap()
+                .len(),
+            5
+        );
+        assert_eq!(
+            crate::RangeParser::new()
+                .limits(limits)
+                .parse_ranges::<u32>(excluded)
+                .unwrap(),
+            vec![1..2, 3..4, 5..6, 7..8, 9..10]
+        );
+        assert_eq!(crate::parse_iter::<u32>(excluded).unwrap().count(), 5);
+    }
+
+    #[test]
+    fn should_count_whole_domain() {
+        let limits = Limits::new().max_items(usize::MAX);
+        assert_eq!(
+            limits.check_items(&[Selection::Include(Segment::Range(0u64, u64::MAX))]),
+            Err(RangeError::TooLarge(u64::MAX as u128 + 1))
+        );
+    }
+}
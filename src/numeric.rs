@@ -0,0 +1,201 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num_traits::{
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, One, ToPrimitive, Zero,
+};
+
+use crate::unit::count_scales;
+use crate::Unit;
+
+/// A wrapper which implements [`Unit`] for any numeric type implementing the `num-traits` traits, so that big
+/// integers (e.g. `num_bigint::BigInt`), fixed-point and decimal types can be parsed without writing a [`Unit`]
+/// impl for each of them.
+///
+/// Values step with the checked arithmetic of `T`, so ranges stop where `T` can't represent the next value.
+/// `T` isn't required to implement [`Bounded`], so unbounded types such as big integers are supported too.
+///
+/// Requires the `num-traits` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use num_bigint::BigInt;
+/// use range_parser::Numeric;
+///
+/// let range: Vec<Numeric<BigInt>> = range_parser::parse("18446744073709551615-18446744073709551617").unwrap();
+/// assert_eq!(range.len(), 3);
+/// assert_eq!(range[2].to_string(), "18446744073709551617");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Numeric<T>(pub T);
+
+impl<T> Numeric<T> {
+    /// Returns the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Numeric<T> {
+    fn from(value: T) -> Self {
+        Numeric(value)
+    }
+}
+
+impl<T> FromStr for Numeric<T>
+where
+    T: FromStr,
+{
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Numeric)
+    }
+}
+
+impl<T> fmt::Display for Numeric<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T> Bounded for Numeric<T>
+where
+    T: Bounded,
+{
+    fn min_value() -> Self {
+        Numeric(T::min_value())
+    }
+
+    fn max_value() -> Self {
+        Numeric(T::max_value())
+    }
+}
+
+impl<T> Unit for Numeric<T>
+where
+    T: PartialOrd
+        + Zero
+        + One
+        + CheckedAdd
+        + CheckedSub
+        + CheckedMul
+        + CheckedDiv
+        + ToPrimitive
+        + FromPrimitive
+        + Clone,
+{
+    fn unit() -> Self {
+        Numeric(T::one())
+    }
+
+    fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+        if start > end || step.0 <= T::zero() {
+            return None;
+        }
+        // the quotient is not negative, so truncating it is the same as flooring it
+        end.0
+            .checked_sub(&start.0)?
+            .checked_div(&step.0)?
+            .to_usize()
+    }
+
+    fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+        step.0
+            .checked_mul(&T::from_usize(count)?)
+            .and_then(|offset| self.0.checked_add(&offset))
+            .map(Numeric)
+    }
+
+    fn backward_checked(&self, count: usize) -> Option<Self> {
+        self.0.checked_sub(&T::from_usize(count)?).map(Numeric)
+    }
+
+    fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+        let count = T::from_usize(count)?;
+        let distance = end.0.checked_sub(&start.0)?;
+        let step = distance.checked_div(&count)?;
+        // integer division truncates the quotient
+        (step.checked_mul(&count)? == distance).then_some(Numeric(step))
+    }
+
+    fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+        num_traits::checked_pow(factor.0.clone(), count)
+            .and_then(|scale| scale.checked_mul(&self.0))
+            .map(Numeric)
+    }
+
+    fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+        if start > end || start.0 <= T::zero() || factor.0 <= T::one() {
+            return None;
+        }
+        Some(count_scales(start, end, |value| value.scale_by(factor, 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_step_big_integers() {
+        let max = Numeric(BigInt::from(u128::MAX));
+        assert_eq!(
+            max.forward_checked(1),
+            Some(Numeric(BigInt::from(u128::MAX) + 1))
+        );
+        assert_eq!(
+            Numeric::steps_between_by(&Numeric(BigInt::from(-5)), &max, &max),
+            Some(1)
+        );
+        assert_eq!(
+            Numeric::steps_between_by(&max, &Numeric(BigInt::from(0)), &Numeric(BigInt::from(1))),
+            None
+        );
+    }
+
+    #[test]
+    fn should_stop_at_the_bounds_of_the_type() {
+        assert_eq!(Numeric(u8::MAX).forward_checked(1), None);
+        assert_eq!(Numeric(0u8).backward_checked(1), None);
+        assert_eq!(
+            Numeric(250u8).forward_by(&Numeric(2), 2),
+            Some(Numeric(254))
+        );
+        assert_eq!(
+            Numeric::<i8>::steps_between_by(&Numeric(-10), &Numeric(10), &Numeric(0)),
+            None
+        );
+        assert_eq!(Numeric::<u8>::max_value(), Numeric(u8::MAX));
+        assert_eq!(Numeric(200u8).scale_by(&Numeric(2), 1), None);
+        assert_eq!(
+            Numeric::<u8>::scales_between_by(&Numeric(1), &Numeric(255), &Numeric(2)),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn should_parse_range_of_big_integers() {
+        let range: Vec<Numeric<BigInt>> =
+            crate::parse("-340282366920938463463374607431768211457--340282366920938463463374607431768211455,0-10:5")
+                .unwrap();
+        assert_eq!(
+            range.into_iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+            vec![
+                "-340282366920938463463374607431768211457",
+                "-340282366920938463463374607431768211456",
+                "-340282366920938463463374607431768211455",
+                "0",
+                "5",
+                "10",
+            ]
+        );
+    }
+}
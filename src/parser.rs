@@ -0,0 +1,1470 @@
+use std::cmp::Ordering;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+
+use crate::lexer::{self, Syntax};
+use crate::segment::{Segment, Selection};
+use crate::{Fragment, Limits, RangeError, RangeIter, RangeResult, Unit};
+
+pub(crate) const AMBIGOUS_RANGE_SEPARATORS: &[&str] = &["--"];
+pub(crate) const WILDCARD: &str = "*";
+
+/// A configurable range parser
+///
+/// The parser is built once with all the options and can then be reused (and shared between threads) to parse
+/// any amount of range strings into any kind of number.
+///
+/// # Default options
+///
+/// - value separator: `,`
+/// - range separator: `-`
+/// - no exclusive range separator
+/// - step separator: `:`
+/// - point count separator: `#`
+/// - factor separator: `*`
+/// - exclusion marker: `!`
+/// - whitespace around values is allowed
+/// - values are neither deduplicated nor sorted
+/// - no [`Limits`]
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::RangeParser;
+///
+/// let parser = RangeParser::new()
+///     .value_separator(";")
+///     .range_separator("..")
+///     .allow_whitespace(false)
+///     .dedup(true)
+///     .sort(true)
+///     .max_items(10_000);
+///
+/// let range: Vec<u32> = parser.parse("8;1..3;2;0").unwrap();
+/// assert_eq!(range, vec![0, 1, 2, 3, 8]);
+/// assert!(parser.parse::<u32>("1; 2").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeParser {
+    value_separator: String,
+    range_separator: String,
+    exclusive_range_separator: Option<String>,
+    step_separator: String,
+    point_count_separator: String,
+    factor_separator: String,
+    exclusion_marker: String,
+    allow_whitespace: bool,
+    dedup: bool,
+    sort: bool,
+    limits: Limits,
+}
+
+impl Default for RangeParser {
+    fn default() -> Self {
+        Self {
+            value_separator: ",".to_string(),
+            range_separator: "-".to_string(),
+            exclusive_range_separator: None,
+            step_separator: ":".to_string(),
+            point_count_separator: "#".to_string(),
+            factor_separator: "*".to_string(),
+            exclusion_marker: "!".to_string(),
+            allow_whitespace: true,
+            dedup: false,
+            sort: false,
+            limits: Limits::default(),
+        }
+    }
+}
+
+impl RangeParser {
+    /// Create a new [`RangeParser`] with the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`RangeParser`] for the Rust range syntax, matching [`std::ops::Range`] and
+    /// [`std::ops::RangeInclusive`] semantics:
+    ///
+    /// - `a..b` goes from `a` to `b`, excluding `b`
+    /// - `a..=b` goes from `a` to `b`, including `b`
+    /// - `..b`, `..=b` and `a..` are open-ended ranges, allowed only when parsing within bounds
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let parser = range_parser::RangeParser::rust();
+    /// assert_eq!(parser.parse::<i32>("0..3,5..=7").unwrap(), vec![0, 1, 2, 5, 6, 7]);
+    /// assert_eq!(parser.parse_bounded::<u8>("..2,8..", 0, 9).unwrap(), vec![0, 1, 8, 9]);
+    /// ```
+    pub fn rust() -> Self {
+        Self::default()
+            .range_separator("..=")
+            .exclusive_range_separator("..")
+    }
+
+    /// Create a new [`RangeParser`] with custom value and range separators, as the `*_with` functions do.
+    ///
+    /// The syntaxes whose default separator clashes with the custom separators (e.g. steps, when `:` is the value
+    /// separator) are disabled, so that range strings which were valid before they were introduced keep parsing.
+    pub(crate) fn with_separators(value_separator: &str, range_separator: &str) -> Self {
+        let parser = Self::new()
+            .value_separator(value_separator)
+            .range_separator(range_separator);
+        let enabled = |separator: &str| {
+            if value_separator.contains(separator) || range_separator.contains(separator) {
+                String::new()
+            } else {
+                separator.to_string()
+            }
+        };
+
+        let step_separator = enabled(&parser.step_separator);
+        let point_count_separator = enabled(&parser.point_count_separator);
+        let factor_separator = enabled(&parser.factor_separator);
+        let exclusion_marker = enabled(&parser.exclusion_marker);
+        parser
+            .step_separator(step_separator)
+            .point_count_separator(point_count_separator)
+            .factor_separator(factor_separator)
+            .exclusion_marker(exclusion_marker)
+    }
+
+    /// Set the separator for single values and ranges
+    ///
+    /// No separator, nor the exclusion marker, can contain another one (e.g. `,` and `,,`), since it would be split
+    /// by it. Only the range separators may contain each other.
+    pub fn value_separator(mut self, value_separator: impl ToString) -> Self {
+        self.value_separator = value_separator.to_string();
+        self
+    }
+
+    /// Set the separator between the start and the end of a range.
+    ///
+    /// It cannot be the same as the other separators and it cannot be `--`, because it's ambiguous since it couldn't
+    /// resolve negative numbers.
+    pub fn range_separator(mut self, range_separator: impl ToString) -> Self {
+        self.range_separator = range_separator.to_string();
+        self
+    }
+
+    /// Set the separator between the start and the end of a range which doesn't include its end (e.g. `..` for
+    /// `0..3`).
+    ///
+    /// If a range contains both the range separator and the exclusive range separator (e.g. `..=` and `..`), the
+    /// longest one is used. Open-ended ranges (e.g. `5..`) always include the upper bound.
+    pub fn exclusive_range_separator(mut self, exclusive_range_separator: impl ToString) -> Self {
+        self.exclusive_range_separator = Some(exclusive_range_separator.to_string());
+        self
+    }
+
+    /// Set the separator between a range and its step. An empty separator disables steps.
+    pub fn step_separator(mut self, step_separator: impl ToString) -> Self {
+        self.step_separator = step_separator.to_string();
+        self
+    }
+
+    /// Set the separator between a range and the amount of evenly spaced points it's divided into (e.g. `#` for
+    /// `0-1#5`, which produces `0, 0.25, 0.5, 0.75, 1`).
+    ///
+    /// Both ends are included, so the amount of points must be at least 2. Integer ranges which can't be divided
+    /// evenly are rejected with [`RangeError::UnevenPoints`]. An empty separator disables point counts.
+    pub fn point_count_separator(mut self, point_count_separator: impl ToString) -> Self {
+        self.point_count_separator = point_count_separator.to_string();
+        self
+    }
+
+    /// Set the separator between a range and the factor its values are multiplied by (e.g. `*` for `1-1000*10`,
+    /// which produces `1, 10, 100, 1000`).
+    ///
+    /// The factor must be greater than one and the start of the range must be positive, otherwise the end couldn't
+    /// be reached. Followed by the point count separator instead of a factor (e.g. `1-1000*#4`), the range is
+    /// divided into an amount of logarithmically spaced points. An empty separator disables factors.
+    pub fn factor_separator(mut self, factor_separator: impl ToString) -> Self {
+        self.factor_separator = factor_separator.to_string();
+        self
+    }
+
+    /// Set the marker which, placed before a segment (e.g. `!5-8`), removes its values from the values accumulated
+    /// by the previous segments.
+    ///
+    /// Exclusions are applied in order, so values added by the following segments are kept. An empty marker
+    /// disables exclusions.
+    pub fn exclusion_marker(mut self, exclusion_marker: impl ToString) -> Self {
+        self.exclusion_marker = exclusion_marker.to_string();
+        self
+    }
+
+    /// Set whether whitespace around values is allowed. If not, any whitespace makes the range string invalid.
+    pub fn allow_whitespace(mut self, allow_whitespace: bool) -> Self {
+        self.allow_whitespace = allow_whitespace;
+        self
+    }
+
+    /// Set whether duplicated values must be removed, keeping the first occurrence of each value
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set whether values must be sorted in ascending order
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Set the maximum amount of values the range can produce, before removing duplicates
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.limits = self.limits.max_items(max_items);
+        self
+    }
+
+    /// Set the maximum amount of segments the range string can contain
+    pub fn max_segments(mut self, max_segments: usize) -> Self {
+        self.limits = self.limits.max_segments(max_segments);
+        self
+    }
+
+    /// Set the [`Limits`] to the size of the range
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Parse a range string to a vector of any kind of number
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let parser = range_parser::RangeParser::new().dedup(true);
+    /// let range: Vec<i32> = parser.parse("-2-1,0,5").unwrap();
+    /// assert_eq!(range, vec![-2, -1, 0, 1, 5]);
+    /// ```
+    pub fn parse<T>(&self, range_str: &str) -> RangeResult<Vec<T>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        self.expand(self.selections(range_str, None)?)
+    }
+
+    /// Parse a range string, which may contain open-ended ranges, to a vector of values between `min` and `max`
+    ///
+    /// See [`crate::parse_bounded`] for the syntax of open-ended ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let parser = range_parser::RangeParser::new().sort(true);
+    /// let range: Vec<u8> = parser.parse_bounded("6-,-2", 1, 8).unwrap();
+    /// assert_eq!(range, vec![1, 2, 6, 7, 8]);
+    /// ```
+    pub fn parse_bounded<T>(&self, range_str: &str, min: T, max: T) -> RangeResult<Vec<T>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        self.expand(self.selections(range_str, Some(&(min..=max)))?)
+    }
+
+    /// Parse a range string to a vector of half-open ranges, without expanding them
+    ///
+    /// Each segment becomes a [`Range`] (e.g. `1-3` becomes `1..4`, `5` becomes `5..6` and, with the Rust
+    /// syntax, `0..3` stays `0..3`), while stepped ranges become a range for each of their values. Empty ranges
+    /// are skipped and excluded values are removed from the previous ranges. This is meant for integer types.
+    ///
+    /// Returns [`RangeError::OutOfBounds`] if the end of a range can't be represented by T (e.g. `0-255` for `u8`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let parser = range_parser::RangeParser::rust();
+    /// let ranges = parser.parse_ranges::<u32>("0..3,5..=7,!6,10").unwrap();
+    /// assert_eq!(ranges, vec![0..3, 5..6, 7..8, 10..11]);
+    /// ```
+    pub fn parse_ranges<T>(&self, range_str: &str) -> RangeResult<Vec<Range<T>>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        self.ranges(range_str, None)
+    }
+
+    /// Parse a range string, which may contain open-ended ranges, to a vector of half-open ranges between `min`
+    /// and `max`
+    ///
+    /// See [`RangeParser::parse_ranges`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let parser = range_parser::RangeParser::rust();
+    /// let ranges = parser.parse_ranges_bounded::<usize>("..2,8..", 0, 9).unwrap();
+    /// assert_eq!(ranges, vec![0..2, 8..10]);
+    /// ```
+    pub fn parse_ranges_bounded<T>(
+        &self,
+        range_str: &str,
+        min: T,
+        max: T,
+    ) -> RangeResult<Vec<Range<T>>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        self.ranges(range_str, Some(&(min..=max)))
+    }
+
+    /// Parse and validate all the segments of a range string, with the operation each of them performs
+    ///
+    /// If `bounds` are provided, open-ended ranges are allowed and all the values must be within the bounds.
+    pub(crate) fn selections<T>(
+        &self,
+        range_str: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Vec<Selection<T>>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let selections = self.parse_parts(range_str, |part| self.parse_selection(part, bounds))?;
+
+        // empty ranges (e.g. `3..3`) have no values
+        Ok(selections.into_iter().flatten().collect())
+    }
+
+    /// Validate the separators, then parse each part of the range string with `parse`, locating errors in the
+    /// range string
+    pub(crate) fn parse_parts<S, F>(&self, range_str: &str, mut parse: F) -> RangeResult<Vec<S>>
+    where
+        F: FnMut(&str) -> RangeResult<S>,
+    {
+        let mut range_separators = vec![&self.range_separator];
+        range_separators.extend(&self.exclusive_range_separator);
+        let mut separators = vec![&self.value_separator];
+        // empty separators disable their syntax
+        separators.extend(
+            [
+                &self.step_separator,
+                &self.point_count_separator,
+                &self.factor_separator,
+                &self.exclusion_marker,
+            ]
+            .into_iter()
+            .filter(|separator| !separator.is_empty()),
+        );
+        // the range separators may contain each other (e.g. `..` and `..=`), since the longest one is used, while
+        // a separator which contains another one would be split by it (e.g. `,` and `,,`)
+        if range_separators[1..].contains(&range_separators[0]) {
+            return Err(RangeError::SeparatorsMustBeDifferent);
+        }
+        for (index, separator) in separators.iter().enumerate() {
+            if separators[index + 1..]
+                .iter()
+                .chain(&range_separators)
+                .any(|other| {
+                    separator.contains(other.as_str()) || other.contains(separator.as_str())
+                })
+            {
+                return Err(RangeError::SeparatorsMustBeDifferent);
+            }
+        }
+        for range_separator in [
+            Some(&self.range_separator),
+            self.exclusive_range_separator.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if AMBIGOUS_RANGE_SEPARATORS.contains(&range_separator.as_str()) {
+                return Err(RangeError::AmbiguousSeparator(range_separator.to_string()));
+            }
+        }
+        self.limits
+            .check_segments(range_str, &self.value_separator)?;
+
+        let mut offset = 0;
+        let mut parsed = Vec::new();
+        for (index, part) in range_str.split(self.value_separator.as_str()).enumerate() {
+            parsed.push(parse(part).map_err(|err| err.locate(part, offset, index))?);
+            offset += part.len() + self.value_separator.len();
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parse a range string to a vector of half-open ranges, applying exclusions, limits, dedup and sort
+    fn ranges<T>(
+        &self,
+        range_str: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Vec<Range<T>>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let selections = self.parse_parts(range_str, |part| {
+            let selection = self.parse_selection(part, bounds)?;
+            // the values are converted once the limits are checked, but the end of the ranges must be representable
+            if let Some(Selection::Include(segment) | Selection::Exclude(segment)) = &selection {
+                if half_open_end(segment).is_none() {
+                    return Err(RangeError::OutOfBounds(Fragment::unlocated(part)));
+                }
+            }
+            Ok(selection)
+        })?;
+        let selections: Vec<Selection<T>> = selections.into_iter().flatten().collect();
+        self.limits.check_items(&selections)?;
+        // the values of stepped and geometric ranges are stepped through one by one
+        check_countable(selections.iter().filter_map(|selection| {
+            let (Selection::Include(segment) | Selection::Exclude(segment)) = selection;
+            Some(segment).filter(|segment| !segment.is_contiguous())
+        }))?;
+
+        let mut ranges: Vec<Range<T>> = Vec::new();
+        for selection in selections {
+            match selection {
+                Selection::Include(segment) => ranges.extend(half_open_ranges(segment)),
+                Selection::Exclude(segment) => {
+                    ranges = ranges
+                        .into_iter()
+                        .flat_map(|range| subtract_segment(range, &segment))
+                        .collect();
+                }
+            }
+        }
+
+        Ok(self.sort_and_dedup(ranges, |a, b| {
+            compare_values(&a.start, &b.start).then(compare_values(&a.end, &b.end))
+        }))
+    }
+
+    /// Expand the segments into a vector with all their values, applying exclusions, limits, dedup and sort
+    fn expand<T>(&self, selections: Vec<Selection<T>>) -> RangeResult<Vec<T>>
+    where
+        T: PartialOrd + Unit + Clone,
+    {
+        self.limits.check_items(&selections)?;
+        check_countable(selections.iter().filter_map(|selection| match selection {
+            Selection::Include(segment) => Some(segment),
+            Selection::Exclude(_) => None,
+        }))?;
+        let range = expand_selections(selections);
+
+        Ok(self.sort_and_dedup(range, compare_values))
+    }
+
+    /// Sort and remove duplicated values, according to the options
+    fn sort_and_dedup<V, F>(&self, mut values: Vec<V>, compare: F) -> Vec<V>
+    where
+        V: PartialEq,
+        F: Fn(&V, &V) -> Ordering,
+    {
+        if self.sort {
+            values.sort_by(&compare);
+            if self.dedup {
+                values.dedup_by(|a, b| a == b);
+            }
+        } else if self.dedup {
+            values = dedup_keeping_order(values, compare);
+        }
+
+        values
+    }
+
+    /// Split a part into the syntax of its range and its optional step (e.g. `1-5:2`), for the grammars built on
+    /// the separators of the parser
+    pub(crate) fn lex_stepped<'a>(
+        &self,
+        part: &'a str,
+    ) -> RangeResult<(Syntax<'a>, Option<&'a str>)> {
+        let (range, step) = split_syntax(part, &self.step_separator);
+        Ok((lexer::lex(range, &self.range_separator)?, step))
+    }
+
+    /// Returns whether `part` is lexed as `syntax` and contains no other syntax of the parser (e.g. a step), so that
+    /// formatted values are parsed back
+    pub(crate) fn lexes_as(&self, part: &str, syntax: Syntax<'_>) -> bool {
+        let separators = [
+            &self.value_separator,
+            &self.step_separator,
+            &self.point_count_separator,
+            &self.factor_separator,
+        ];
+        separators
+            .into_iter()
+            .chain(&self.exclusive_range_separator)
+            .all(|separator| separator.is_empty() || !part.contains(separator.as_str()))
+            && (self.exclusion_marker.is_empty()
+                || !part.starts_with(self.exclusion_marker.as_str()))
+            && lexer::lex(part, &self.range_separator) == Ok(syntax)
+    }
+
+    /// Parse a range part, which may start with the exclusion marker, to a selection of T
+    fn parse_selection<T>(
+        &self,
+        part: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Option<Selection<T>>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        if !self.allow_whitespace && part.contains(char::is_whitespace) {
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+        }
+
+        match part
+            .trim_start()
+            .strip_prefix(self.exclusion_marker.as_str())
+        {
+            Some(excluded) if !self.exclusion_marker.is_empty() => self
+                .parse_part(excluded, bounds)
+                .map(|segment| segment.map(Selection::Exclude)),
+            _ => self
+                .parse_part(part, bounds)
+                .map(|segment| segment.map(Selection::Include)),
+        }
+    }
+
+    /// Parse a range part to a segment of T
+    ///
+    /// Returns `None` if the range is empty, because its end is excluded (e.g. `3..3`).
+    fn parse_part<T>(
+        &self,
+        part: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Option<Segment<T>>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let (range, step) = split_syntax(part, &self.step_separator);
+        let (range, points) = split_syntax(range, &self.point_count_separator);
+        // the factor is the last one, so that the wildcard can be a range (e.g. `**2`)
+        let (range, factor) = match range.rsplit_once(self.factor_separator.as_str()) {
+            Some((start, factor))
+                if range.trim() != WILDCARD && !self.factor_separator.is_empty() =>
+            {
+                (start, Some(factor))
+            }
+            _ => (range, None),
+        };
+        let range_separator = self.find_range_separator(range);
+
+        let segment = if let Some(factor) = factor {
+            // a range can have either a step or a factor
+            if step.is_some() {
+                return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+            }
+            self.parse_geometric_range(part, range, factor, points, range_separator, bounds)?
+        } else if let Some(points) = points {
+            // a range can have either a step or an amount of points
+            if step.is_some() {
+                return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+            }
+            self.parse_points_range(part, range, points, range_separator, bounds)?
+        } else if let Some(step) = step {
+            self.parse_stepped_range(part, range, step, range_separator, bounds)?
+        } else if part.contains(range_separator) || (bounds.is_some() && part.trim() == WILDCARD) {
+            self.parse_value_range(part, range_separator, bounds)?
+        } else {
+            parse_as_t(part).map(Segment::Value)?
+        };
+
+        // the end is excluded, unless the range is open-ended (e.g. `5..`)
+        let range = range.trim();
+        let segment = match self.exclusive_range_separator.as_deref() {
+            Some(exclusive)
+                if exclusive == range_separator
+                    && range != WILDCARD
+                    && !range.ends_with(exclusive) =>
+            {
+                match exclude_end(segment) {
+                    Some(segment) => segment,
+                    None => return Ok(None),
+                }
+            }
+            _ => segment,
+        };
+
+        if let Some(bounds) = bounds {
+            let (start, end) = match &segment {
+                Segment::Value(value) => (value, value),
+                Segment::Range(start, end)
+                | Segment::SteppedRange(start, end, _)
+                | Segment::GeometricRange(start, end, _) => (start, end),
+            };
+            if !bounds.contains(start) || !bounds.contains(end) {
+                return Err(RangeError::OutOfBounds(Fragment::unlocated(part)));
+            }
+        }
+
+        Ok(Some(segment))
+    }
+
+    /// Returns the range separator which divides `range`, which is the longest one if `range` contains both the
+    /// inclusive and the exclusive range separators
+    fn find_range_separator(&self, range: &str) -> &str {
+        match self.exclusive_range_separator.as_deref() {
+            Some(exclusive)
+                if range.contains(exclusive)
+                    && (!range.contains(self.range_separator.as_str())
+                        || exclusive.len() > self.range_separator.len()) =>
+            {
+                exclusive
+            }
+            _ => &self.range_separator,
+        }
+    }
+
+    /// Parse a range with a step (e.g. `0-10:2`) to a segment of T
+    fn parse_stepped_range<T>(
+        &self,
+        part: &str,
+        range: &str,
+        step: &str,
+        range_separator: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Segment<T>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let Segment::Range(start, end) = self.parse_value_range(range, range_separator, bounds)?
+        else {
+            // a step is allowed only after a range
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+        };
+        let step: T = parse_as_t(step)?;
+
+        stepped_segment(part, start, end, step)
+    }
+
+    /// Parse a range divided into an amount of evenly spaced points (e.g. `0-1#5`) to a segment of T
+    fn parse_points_range<T>(
+        &self,
+        part: &str,
+        range: &str,
+        points: &str,
+        range_separator: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Segment<T>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let Segment::Range(start, end) = self.parse_value_range(range, range_separator, bounds)?
+        else {
+            // an amount of points is allowed only after a range
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+        };
+        let steps = parse_point_steps(part, points)?;
+        let step = T::step_dividing(&start, &end, steps)
+            .ok_or_else(|| RangeError::UnevenPoints(Fragment::unlocated(part)))?;
+
+        stepped_segment(part, start, end, step)
+    }
+
+    /// Parse a range multiplied by a factor (e.g. `1-1000*10`), or divided into an amount of logarithmically spaced
+    /// points (e.g. `1-1000*#4`), to a segment of T
+    fn parse_geometric_range<T>(
+        &self,
+        part: &str,
+        range: &str,
+        factor: &str,
+        points: Option<&str>,
+        range_separator: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Segment<T>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let Segment::Range(start, end) = self.parse_value_range(range, range_separator, bounds)?
+        else {
+            // a factor is allowed only after a range
+            return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+        };
+        let factor: T = match points {
+            Some(points) if factor.trim().is_empty() => {
+                let scales = parse_point_steps(part, points)?;
+                T::factor_dividing(&start, &end, scales)
+                    .ok_or_else(|| RangeError::UnevenPoints(Fragment::unlocated(part)))?
+            }
+            None => parse_as_t(factor)?,
+            // a range can have either a factor or an amount of points
+            Some(_) => return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part))),
+        };
+
+        geometric_segment(part, start, end, factor)
+    }
+
+    /// Parse value range to a segment of T
+    ///
+    /// If the range is `1-3`, it will return a range segment from 1 to 3.
+    /// If the range is a single value (e.g. `-5`), it will return a value segment.
+    /// See [`crate::lexer`] for the grammar.
+    fn parse_value_range<T>(
+        &self,
+        part: &str,
+        range_separator: &str,
+        bounds: Option<&RangeInclusive<T>>,
+    ) -> RangeResult<Segment<T>>
+    where
+        T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+    {
+        let (start, end): (T, T) = match (lexer::lex(part, range_separator)?, bounds) {
+            (Syntax::Value(WILDCARD), Some(bounds)) => {
+                (bounds.start().clone(), bounds.end().clone())
+            }
+            // with `-` as separator, a leading `-` is a minus sign if the value is a valid number,
+            // otherwise the range is open-ended (e.g. `-5` for unsigned numbers)
+            (Syntax::Value(value), Some(bounds)) => match value.strip_prefix(range_separator) {
+                Some(end) if parse_as_t::<T>(value).is_err() => {
+                    (bounds.start().clone(), parse_as_t(end)?)
+                }
+                _ => return parse_as_t(value).map(Segment::Value),
+            },
+            (Syntax::Value(value), None) => return parse_as_t(value).map(Segment::Value),
+            (Syntax::Range(Some(start), Some(end)), _) => (parse_as_t(start)?, parse_as_t(end)?),
+            // open-ended ranges take the missing values from the bounds
+            (Syntax::Range(start, end), Some(bounds)) => (
+                start.map_or_else(|| Ok(bounds.start().clone()), parse_as_t)?,
+                end.map_or_else(|| Ok(bounds.end().clone()), parse_as_t)?,
+            ),
+            (Syntax::Range(..), None) => {
+                return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)))
+            }
+        };
+
+        // if start is bigger than end, it's an invalid range
+        if start > end {
+            return Err(RangeError::StartBiggerThanEnd(Fragment::unlocated(part)));
+        }
+
+        Ok(Segment::Range(start, end))
+    }
+}
+
+/// Parse a string to a T
+pub(crate) fn parse_as_t<T>(part: &str) -> RangeResult<T>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    part.trim().parse().map_err(|_| {
+        if T::is_forbidden_zero(part) {
+            RangeError::ZeroValue(Fragment::unlocated(part))
+        } else {
+            RangeError::NotANumber(Fragment::unlocated(part))
+        }
+    })
+}
+
+/// Check that the values of the segments can be counted by `usize`, so that they can be expanded
+pub(crate) fn check_countable<'a, T, I>(segments: I) -> RangeResult<()>
+where
+    T: Unit + 'a,
+    I: IntoIterator<Item = &'a Segment<T>>,
+{
+    match segments
+        .into_iter()
+        .find(|segment| segment.steps().is_none())
+    {
+        Some(segment) => Err(RangeError::TooLarge(segment.count())),
+        None => Ok(()),
+    }
+}
+
+/// Split `part` at `separator`, unless the separator is empty because its syntax is disabled
+fn split_syntax<'a>(part: &'a str, separator: &str) -> (&'a str, Option<&'a str>) {
+    match part.split_once(separator).filter(|_| !separator.is_empty()) {
+        Some((range, syntax)) => (range, Some(syntax)),
+        None => (part, None),
+    }
+}
+
+/// Validate the step of a range, returning the stepped segment from `start` to `end`
+fn stepped_segment<T>(part: &str, start: T, end: T, step: T) -> RangeResult<Segment<T>>
+where
+    T: PartialOrd + Unit,
+{
+    // the value before the unit is zero for numbers, which doesn't move when stepping by itself
+    match T::unit().backward_checked(1) {
+        Some(zero)
+            if zero
+                .forward_by(&zero, 1)
+                .filter(|value| *value != zero)
+                .is_none() =>
+        {
+            match step.partial_cmp(&zero) {
+                Some(Ordering::Equal) => {
+                    return Err(RangeError::ZeroStep(Fragment::unlocated(part)))
+                }
+                Some(Ordering::Less) => {
+                    return Err(RangeError::NegativeStep(Fragment::unlocated(part)))
+                }
+                Some(Ordering::Greater) => {}
+                // e.g. `NaN`
+                None => return Err(RangeError::NotANumber(Fragment::unlocated(part))),
+            }
+        }
+        // the unit is the smallest positive step of the types without zero (e.g. `NonZeroI32`)
+        _ if step < T::unit() => return Err(RangeError::NegativeStep(Fragment::unlocated(part))),
+        _ => {}
+    }
+    // e.g. steps other than the unit for chars
+    if T::steps_between_by(&start, &start, &step).is_none() {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+    }
+
+    Ok(Segment::SteppedRange(start, end, step))
+}
+
+/// Parse the amount of points of a range (e.g. `5` in `0-1#5`), returning the amount of steps between them
+fn parse_point_steps(part: &str, points: &str) -> RangeResult<usize> {
+    let points: usize = points
+        .trim()
+        .parse()
+        .map_err(|_| RangeError::NotANumber(Fragment::unlocated(points)))?;
+    // both ends are points of the range
+    points
+        .checked_sub(1)
+        .filter(|steps| *steps > 0)
+        .ok_or_else(|| RangeError::InvalidRangeSyntax(Fragment::unlocated(part)))
+}
+
+/// Validate the factor of a range, returning the geometric segment from `start` to `end`
+fn geometric_segment<T>(part: &str, start: T, end: T, factor: T) -> RangeResult<Segment<T>>
+where
+    T: PartialOrd + Unit,
+{
+    if factor.partial_cmp(&T::unit()) != Some(Ordering::Greater) {
+        return Err(RangeError::InvalidFactor(Fragment::unlocated(part)));
+    }
+    // multiplying a start which is not positive never grows towards the end
+    if T::unit()
+        .backward_checked(1)
+        .is_some_and(|zero| start <= zero)
+    {
+        return Err(RangeError::UnreachableEnd(Fragment::unlocated(part)));
+    }
+    // e.g. chars, which can't be multiplied
+    if T::scales_between_by(&start, &start, &factor).is_none() {
+        return Err(RangeError::InvalidRangeSyntax(Fragment::unlocated(part)));
+    }
+    Ok(Segment::GeometricRange(start, end, factor))
+}
+
+/// Expand the segments into a vector with all their values, removing excluded values in order
+fn expand_selections<T>(selections: Vec<Selection<T>>) -> Vec<T>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    let mut range: Vec<T> = Vec::new();
+
+    for selection in selections {
+        match selection {
+            Selection::Include(Segment::Value(value)) => range.push(value),
+            Selection::Include(Segment::Range(start, end)) => {
+                push_range(&mut range, start, end, T::unit())
+            }
+            Selection::Include(Segment::SteppedRange(start, end, step)) => {
+                push_range(&mut range, start, end, step)
+            }
+            Selection::Include(segment @ Segment::GeometricRange(..)) => {
+                range.extend(RangeIter::new(vec![segment]))
+            }
+            Selection::Exclude(segment) => range.retain(|value| !segment.contains(value)),
+        }
+    }
+
+    range
+}
+
+/// Push all the values from `start` to `end`, advancing by `step`, to the accumulator.
+///
+/// Each value is computed as `start + i * step`, rather than by repeatedly adding `step` to the previous value,
+/// so that floats don't accumulate rounding errors.
+fn push_range<T>(acc: &mut Vec<T>, start: T, end: T, step: T)
+where
+    T: PartialOrd + Unit + Clone,
+{
+    let steps = T::steps_between_by(&start, &end, &step).unwrap_or_default();
+    for i in 0..=steps {
+        // stop when a value can't be represented by T (e.g. the range ends at `T::MAX`) or, for floats,
+        // when it's rounded to the previous value
+        match start.forward_by(&step, i) {
+            // for floats, the last value overshoots the end only when the steps are rounded up to it, because
+            // `start + steps * step` rounds to the end
+            Some(value) if i == steps && value > end => {
+                if acc.last().is_none_or(|last| *last < end) {
+                    acc.push(end);
+                }
+                break;
+            }
+            Some(value)
+                if value <= end && (i == 0 || acc.last().is_some_and(|last| *last < value)) =>
+            {
+                acc.push(value)
+            }
+            _ => break,
+        }
+    }
+}
+
+/// Remove the end from a range segment, returning `None` if the range becomes empty
+fn exclude_end<T>(segment: Segment<T>) -> Option<Segment<T>>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    match segment {
+        Segment::Value(value) => Some(Segment::Value(value)),
+        Segment::Range(start, end) => match last_before(&start, &end, &T::unit()) {
+            Some(last) => Some(Segment::Range(start, last)),
+            // the last value of a range too large to be counted is found stepping back from the end
+            None if T::steps_between(&start, &end).is_none() => {
+                let last = end.backward_checked(1);
+                Some(Segment::Range(start, last.unwrap_or(end)))
+            }
+            None => None,
+        },
+        Segment::SteppedRange(start, end, step) => match last_before(&start, &end, &step) {
+            Some(last) => Some(Segment::SteppedRange(start, last, step)),
+            // a stepped range too large to be counted can't be expanded anyway
+            None if T::steps_between_by(&start, &end, &step).is_none() => {
+                Some(Segment::SteppedRange(start, end, step))
+            }
+            None => None,
+        },
+        Segment::GeometricRange(start, end, factor) => last_scaled_before(&start, &end, &factor)
+            .map(|end| Segment::GeometricRange(start, end, factor)),
+    }
+}
+
+/// Returns the last value from `start`, advancing by `step`, which is smaller than `end`
+fn last_before<T>(start: &T, end: &T, step: &T) -> Option<T>
+where
+    T: PartialOrd + Unit,
+{
+    let steps = T::steps_between_by(start, end, step)?;
+    match start.forward_by(step, steps) {
+        Some(last) if last < *end => Some(last),
+        _ => steps
+            .checked_sub(1)
+            .and_then(|steps| start.forward_by(step, steps)),
+    }
+}
+
+/// Returns the last value from `start`, multiplying by `factor`, which is smaller than `end`
+fn last_scaled_before<T>(start: &T, end: &T, factor: &T) -> Option<T>
+where
+    T: PartialOrd + Unit,
+{
+    let scales = T::scales_between_by(start, end, factor)?;
+    match start.scale_by(factor, scales) {
+        Some(last) if last < *end => Some(last),
+        _ => scales
+            .checked_sub(1)
+            .and_then(|scales| start.scale_by(factor, scales)),
+    }
+}
+
+/// Returns the end of the half-open ranges of a segment, which is the value after its last one, or `None` if it
+/// can't be represented by T
+fn half_open_end<T>(segment: &Segment<T>) -> Option<T>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    let last = match segment {
+        Segment::Value(last) | Segment::Range(_, last) => last.clone(),
+        Segment::SteppedRange(_, end, _) if segment.is_contiguous() => end.clone(),
+        // the last value of stepped and geometric ranges is computed without expanding them
+        segment => RangeIter::new(vec![segment.clone()]).next_back()?,
+    };
+    last.forward_checked(1)
+}
+
+/// Convert a segment, whose end is representable, to half-open ranges
+fn half_open_ranges<T>(segment: Segment<T>) -> Vec<Range<T>>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    if segment.is_contiguous() {
+        let end = half_open_end(&segment);
+        let (Segment::Value(start)
+        | Segment::Range(start, _)
+        | Segment::SteppedRange(start, ..)
+        | Segment::GeometricRange(start, ..)) = segment;
+        return end.map(|end| vec![start..end]).unwrap_or_default();
+    }
+    // values of stepped and geometric ranges are not contiguous
+    value_ranges(RangeIter::new(vec![segment]))
+}
+
+/// Convert each value to the half-open range containing only it
+fn value_ranges<T, I>(values: I) -> Vec<Range<T>>
+where
+    T: Unit + Clone,
+    I: IntoIterator<Item = T>,
+{
+    values
+        .into_iter()
+        .filter_map(|value| {
+            let end = value.forward_checked(1)?;
+            Some(value..end)
+        })
+        .collect()
+}
+
+/// Remove the values of an excluded segment from `range`, without expanding the values outside of it
+fn subtract_segment<T>(range: Range<T>, excluded: &Segment<T>) -> Vec<Range<T>>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    let excluded = if excluded.is_contiguous() {
+        half_open_ranges(excluded.clone())
+    } else {
+        // the values of stepped and geometric ranges are computed from the start of the range
+        value_ranges(
+            RangeIter::from_value(excluded.clone(), &range.start)
+                .take_while(|value| *value < range.end),
+        )
+    };
+    subtract_ranges(range, excluded)
+}
+
+/// Remove the excluded ranges, which must be sorted and not overlapping, from `range`
+fn subtract_ranges<T>(range: Range<T>, excluded: Vec<Range<T>>) -> Vec<Range<T>>
+where
+    T: PartialOrd + Clone,
+{
+    let mut ranges = Vec::new();
+    let mut start = range.start;
+    for excluded in excluded {
+        if excluded.end <= start {
+            continue;
+        }
+        if excluded.start > start {
+            let end = if excluded.start < range.end {
+                excluded.start
+            } else {
+                range.end.clone()
+            };
+            ranges.push(start..end);
+        }
+        start = excluded.end;
+        if start >= range.end {
+            return ranges;
+        }
+    }
+    ranges.push(start..range.end);
+    ranges
+}
+
+/// Compare two values, considering incomparable values (e.g. `NaN`) as equal
+fn compare_values<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Remove duplicated values, keeping the first occurrence of each value and the order of the values
+fn dedup_keeping_order<V, F>(values: Vec<V>, compare: F) -> Vec<V>
+where
+    V: PartialEq,
+    F: Fn(&V, &V) -> Ordering,
+{
+    // sort the indexes by value, so duplicates are adjacent and the first occurrence comes first
+    let mut indexes: Vec<usize> = (0..values.len()).collect();
+    indexes.sort_by(|a, b| compare(&values[*a], &values[*b]).then(a.cmp(b)));
+
+    let mut keep = vec![true; values.len()];
+    for pair in indexes.windows(2) {
+        if values[pair[0]] == values[pair[1]] {
+            keep[pair[1]] = false;
+        }
+    }
+
+    values
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(value, keep)| keep.then_some(value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_with_default_options() {
+        let range: Vec<i32> = RangeParser::new().parse("-2,0-3,-1,7").unwrap();
+        assert_eq!(range, vec![-2, 0, 1, 2, 3, -1, 7]);
+    }
+
+    #[test]
+    fn should_parse_with_custom_separators() {
+        let parser = RangeParser::new()
+            .value_separator(";")
+            .range_separator("..")
+            .step_separator("/");
+        let range: Vec<i32> = parser.parse("-2;0..6/3;-1").unwrap();
+        assert_eq!(range, vec![-2, 0, 3, 6, -1]);
+    }
+
+    #[test]
+    fn should_not_allow_whitespace() {
+        let parser = RangeParser::new().allow_whitespace(false);
+        assert_eq!(parser.parse::<u32>("1,3-5").unwrap(), vec![1, 3, 4, 5]);
+        assert_eq!(
+            parser.parse::<u32>("1,3 -5").unwrap_err(),
+            RangeError::InvalidRangeSyntax(Fragment::new("3 -5", 2..6, 1))
+        );
+        assert!(parser.parse::<u32>("1\t").is_err());
+    }
+
+    #[test]
+    fn should_dedup_keeping_order() {
+        let parser = RangeParser::new().dedup(true);
+        let range: Vec<u32> = parser.parse("5,1-3,2,5,0").unwrap();
+        assert_eq!(range, vec![5, 1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn should_sort() {
+        let parser = RangeParser::new().sort(true);
+        let range: Vec<f64> = parser.parse("5,1-3,2").unwrap();
+        assert_eq!(range, vec![1.0, 2.0, 2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn should_sort_and_dedup() {
+        let parser = RangeParser::new().sort(true).dedup(true);
+        let range: Vec<i8> = parser.parse("5,1-3,2,-1,5").unwrap();
+        assert_eq!(range, vec![-1, 1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn should_apply_limits() {
+        let parser = RangeParser::new().max_items(3).max_segments(2);
+        assert_eq!(parser.parse::<u32>("1-2,3").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            parser.parse::<u32>("1-3,3").unwrap_err(),
+            RangeError::TooLarge(4)
+        );
+        assert_eq!(
+            parser.parse::<u32>("1,2,3").unwrap_err(),
+            RangeError::TooManySegments(3)
+        );
+        let parser = RangeParser::new().limits(Limits::new().max_items(1));
+        assert_eq!(
+            parser.parse::<u32>("1-2").unwrap_err(),
+            RangeError::TooLarge(2)
+        );
+    }
+
+    #[test]
+    fn should_apply_limits_to_ranges_before_converting_them() {
+        assert_eq!(
+            RangeParser::rust()
+                .max_items(10)
+                .parse_ranges::<u64>("0..=50000000:2")
+                .unwrap_err(),
+            RangeError::TooLarge(25000001)
+        );
+        // excluded values outside of the included ranges are never produced
+        assert_eq!(
+            RangeParser::rust()
+                .max_items(100)
+                .parse_ranges::<u64>("0..10,!0..=60000000:2")
+                .unwrap(),
+            vec![1..2, 3..4, 5..6, 7..8, 9..10]
+        );
+        assert_eq!(
+            RangeParser::rust()
+                .parse_ranges::<u64>("0..=4000000000,!1..=4000000000*2,!100..=4000000000")
+                .unwrap(),
+            vec![0..1, 3..4, 5..8, 9..16, 17..32, 33..64, 65..100]
+        );
+        assert_eq!(
+            RangeParser::rust()
+                .parse_ranges::<u32>("0..=20,!5..=4000000000:5")
+                .unwrap(),
+            vec![0..5, 6..10, 11..15, 16..20]
+        );
+    }
+
+    #[test]
+    fn should_parse_bounded_with_options() {
+        let parser = RangeParser::new().sort(true).dedup(true);
+        let range: Vec<u8> = parser.parse_bounded("6-,-2,*:4", 1, 8).unwrap();
+        assert_eq!(range, vec![1, 2, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn should_exclude_with_custom_marker() {
+        let parser = RangeParser::new().exclusion_marker("^");
+        assert_eq!(parser.parse::<u32>("1-5,^2-3").unwrap(), vec![1, 4, 5]);
+        assert!(parser.parse::<u32>("1-5,!2").is_err());
+        let parser = RangeParser::new().exclusion_marker("");
+        assert!(parser.parse::<u32>("1-5,!2").is_err());
+    }
+
+    #[test]
+    fn should_exclude_before_sort_and_dedup() {
+        let parser = RangeParser::new().sort(true).dedup(true);
+        assert_eq!(
+            parser.parse::<u32>("5,1-3,1,!1,1").unwrap(),
+            vec![1, 2, 3, 5]
+        );
+    }
+
+    #[test]
+    fn should_parse_rust_ranges() {
+        let parser = RangeParser::rust();
+        assert_eq!(parser.parse::<u32>("0..3").unwrap(), vec![0, 1, 2]);
+        assert_eq!(parser.parse::<u32>("0..=3").unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            parser.parse::<i32>("-5..-3,-1..=1,7").unwrap(),
+            vec![-5, -4, -1, 0, 1, 7]
+        );
+        assert_eq!(parser.parse::<u32>("3..3,4").unwrap(), vec![4]);
+        assert_eq!(
+            parser.parse::<u8>("250..=255").unwrap(),
+            vec![250, 251, 252, 253, 254, 255]
+        );
+        assert_eq!(parser.parse::<f64>("0..2.5").unwrap(), vec![0.0, 1.0, 2.0]);
+        assert_eq!(
+            parser.parse::<u32>("4..3").unwrap_err(),
+            RangeError::StartBiggerThanEnd(Fragment::new("4..3", 0..4, 0))
+        );
+    }
+
+    #[test]
+    fn should_parse_rust_ranges_with_step() {
+        let parser = RangeParser::rust();
+        assert_eq!(parser.parse::<u32>("0..10:5").unwrap(), vec![0, 5]);
+        assert_eq!(parser.parse::<u32>("0..=10:5").unwrap(), vec![0, 5, 10]);
+        assert_eq!(parser.parse::<u32>("0..9:5").unwrap(), vec![0, 5]);
+        assert_eq!(
+            parser.parse::<f64>("0..1:0.25").unwrap(),
+            vec![0.0, 0.25, 0.5, 0.75]
+        );
+    }
+
+    #[test]
+    fn should_parse_open_ended_rust_ranges() {
+        let parser = RangeParser::rust();
+        assert_eq!(
+            parser.parse_bounded::<u8>("..2,..=3,8..", 0, 9).unwrap(),
+            vec![0, 1, 0, 1, 2, 3, 8, 9]
+        );
+        assert_eq!(
+            parser.parse_bounded::<i8>("..-8,7..", -10, 8).unwrap(),
+            vec![-10, -9, 7, 8]
+        );
+        assert_eq!(
+            parser.parse_bounded::<u8>("..10", 0, 9).unwrap(),
+            vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+        );
+        assert!(parser.parse::<u8>("..2").is_err());
+    }
+
+    #[test]
+    fn should_use_longest_range_separator() {
+        let parser = RangeParser::new().exclusive_range_separator("..");
+        assert_eq!(
+            parser.parse::<i32>("-3..-1,1-2").unwrap(),
+            vec![-3, -2, 1, 2]
+        );
+    }
+
+    #[test]
+    fn should_parse_half_open_ranges() {
+        let parser = RangeParser::rust();
+        assert_eq!(
+            parser.parse_ranges::<u32>("0..3,5..=7,9,0..10:4").unwrap(),
+            vec![0..3, 5..8, 9..10, 0..1, 4..5, 8..9]
+        );
+        assert_eq!(
+            parser.parse_ranges::<u32>("0..10,!3..5,!7").unwrap(),
+            vec![0..3, 5..7, 8..10]
+        );
+        assert_eq!(parser.parse_ranges::<u32>("3..3").unwrap(), vec![]);
+        assert_eq!(
+            parser
+                .parse_ranges_bounded::<usize>("..2,8..", 0, 9)
+                .unwrap(),
+            vec![0..2, 8..10]
+        );
+        assert_eq!(
+            RangeParser::new().parse_ranges::<i32>("-3--1,0").unwrap(),
+            vec![-3..0, 0..1]
+        );
+    }
+
+    #[test]
+    fn should_sort_and_dedup_half_open_ranges() {
+        let parser = RangeParser::rust().dedup(true);
+        assert_eq!(
+            parser.parse_ranges::<u32>("5..7,0..3,5..7").unwrap(),
+            vec![5..7, 0..3]
+        );
+        let parser = parser.sort(true);
+        assert_eq!(
+            parser.parse_ranges::<u32>("5..7,0..3,5..7,0..2").unwrap(),
+            vec![0..2, 0..3, 5..7]
+        );
+    }
+
+    #[test]
+    fn should_not_parse_half_open_range_ending_at_max() {
+        assert_eq!(
+            RangeParser::rust()
+                .parse_ranges::<u8>("0,250..=255")
+                .unwrap_err(),
+            RangeError::OutOfBounds(Fragment::new("250..=255", 2..11, 1))
+        );
+    }
+
+    #[test]
+    fn should_parse_point_count_with_custom_separator() {
+        let parser = RangeParser::rust().point_count_separator(" in ");
+        assert_eq!(
+            parser.parse::<f64>("0..=1 in 3").unwrap(),
+            vec![0.0, 0.5, 1.0]
+        );
+        assert_eq!(parser.parse::<i32>("0..8 in 5").unwrap(), vec![0, 2, 4, 6]);
+    }
+
+    #[test]
+    fn should_parse_geometric_range_with_custom_separator() {
+        let parser = RangeParser::rust().factor_separator(" x ");
+        assert_eq!(
+            parser.parse::<u32>("1..=1000 x 10").unwrap(),
+            vec![1, 10, 100, 1000]
+        );
+        assert_eq!(
+            parser.parse::<u32>("1..1000 x 10").unwrap(),
+            vec![1, 10, 100]
+        );
+        assert_eq!(
+            parser.parse_ranges::<u32>("1..=100 x 10").unwrap(),
+            vec![1..2, 10..11, 100..101]
+        );
+    }
+
+    #[test]
+    fn should_validate_separators() {
+        assert_eq!(
+            RangeParser::new()
+                .value_separator("-")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .point_count_separator(":")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .factor_separator("#")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::rust()
+                .value_separator("..")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .exclusive_range_separator("--")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::AmbiguousSeparator("--".to_string())
+        );
+        assert_eq!(
+            RangeParser::new()
+                .exclusion_marker(":")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .range_separator("--")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::AmbiguousSeparator("--".to_string())
+        );
+    }
+
+    #[test]
+    fn should_not_allow_separators_containing_each_other() {
+        assert_eq!(
+            RangeParser::new()
+                .step_separator(",,")
+                .parse::<u32>("1-5,,2")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .step_separator("-:")
+                .parse::<u32>("1-5-:2")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .value_separator(";")
+                .exclusion_marker(";!")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        assert_eq!(
+            RangeParser::new()
+                .exclusive_range_separator("-")
+                .parse::<u32>("1")
+                .unwrap_err(),
+            RangeError::SeparatorsMustBeDifferent
+        );
+        // the longest range separator is used
+        assert_eq!(
+            RangeParser::new()
+                .range_separator("..=")
+                .exclusive_range_separator("..")
+                .parse::<u32>("1..3,5..=6")
+                .unwrap(),
+            vec![1, 2, 5, 6]
+        );
+    }
+
+    #[test]
+    fn should_be_reusable_across_threads() {
+        fn assert_send_sync<T: Clone + Send + Sync>() {}
+        assert_send_sync::<RangeParser>();
+
+        let parser = RangeParser::new().sort(true);
+        let handle = {
+            let parser = parser.clone();
+            std::thread::spawn(move || parser.parse::<u32>("3,1-2").unwrap())
+        };
+        assert_eq!(handle.join().unwrap(), vec![1, 2, 3]);
+        assert_eq!(parser.parse::<u32>("9,8").unwrap(), vec![8, 9]);
+    }
+
+    #[test]
+    fn should_dedup_values_keeping_first_occurrence() {
+        assert_eq!(
+            dedup_keeping_order(vec![3, 1, 3, 2, 1], compare_values),
+            vec![3, 1, 2]
+        );
+        assert_eq!(
+            dedup_keeping_order::<u8, _>(vec![], compare_values),
+            Vec::<u8>::new()
+        );
+    }
+}
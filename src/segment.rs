@@ -0,0 +1,110 @@
+use crate::Unit;
+
+/// A segment of a range string, as delimited by the value separator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Segment<T> {
+    /// A single value (e.g. `3`)
+    Value(T),
+    /// An inclusive range of values (e.g. `1-3`)
+    Range(T, T),
+    /// An inclusive range of values advancing by a step (e.g. `0-10:2`)
+    SteppedRange(T, T, T),
+    /// An inclusive range of values multiplied by a factor (e.g. `1-1000*10`)
+    GeometricRange(T, T, T),
+}
+
+/// A segment with the operation it performs on the values accumulated by the previous segments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Selection<T> {
+    /// The values of the segment are appended (e.g. `1-3`)
+    Include(Segment<T>),
+    /// The values of the segment are removed from the values accumulated so far (e.g. `!1-3`)
+    Exclude(Segment<T>),
+}
+
+impl<T> Segment<T>
+where
+    T: Unit,
+{
+    /// Returns the amount of steps from the first value of the segment to the last one, or `None` if they can't be
+    /// counted by `usize`, so that the segment is too large to be expanded
+    pub(crate) fn steps(&self) -> Option<usize> {
+        match self {
+            Self::Value(_) => Some(0),
+            Self::Range(start, end) => T::steps_between(start, end),
+            Self::SteppedRange(start, end, step) => T::steps_between_by(start, end, step),
+            Self::GeometricRange(start, end, factor) => T::scales_between_by(start, end, factor),
+        }
+    }
+
+    /// Returns the amount of values in the segment, without expanding it.
+    ///
+    /// The amount saturates at `u128::MAX` when the steps can't be counted by `usize`.
+    pub(crate) fn count(&self) -> u128 {
+        self.steps().map_or(u128::MAX, |steps| steps as u128 + 1)
+    }
+}
+
+impl<T> Segment<T>
+where
+    T: PartialOrd + Unit,
+{
+    /// Returns whether the values of the segment are contiguous, so that it's a single interval
+    pub(crate) fn is_contiguous(&self) -> bool {
+        match self {
+            Self::Value(_) | Self::Range(..) => true,
+            Self::SteppedRange(_, _, step) => *step == T::unit(),
+            Self::GeometricRange(..) => false,
+        }
+    }
+
+    /// Returns whether the segment contains `value`, without expanding it
+    pub(crate) fn contains(&self, value: &T) -> bool {
+        match self {
+            Self::Value(x) => x == value,
+            Self::Range(start, end) => start <= value && value <= end,
+            Self::SteppedRange(start, end, step) => {
+                start <= value
+                    && value <= end
+                    && T::steps_between_by(start, value, step)
+                        .and_then(|steps| start.forward_by(step, steps))
+                        .is_some_and(|x| x == *value)
+            }
+            Self::GeometricRange(start, end, factor) => {
+                start <= value
+                    && value <= end
+                    && T::scales_between_by(start, value, factor)
+                        .and_then(|scales| start.scale_by(factor, scales))
+                        .is_some_and(|x| x == *value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_values_of_segment() {
+        assert_eq!(Segment::Value(3).count(), 1);
+        assert_eq!(Segment::SteppedRange(0u8, 10, 4).count(), 3);
+        assert_eq!(Segment::GeometricRange(1u64, 1000, 10).count(), 4);
+        assert_eq!(Segment::GeometricRange(3u64, 1000, 2).count(), 9);
+        assert_eq!(Segment::Range(0u128, u128::MAX).count(), u128::MAX);
+    }
+
+    #[test]
+    fn should_tell_whether_segment_contains_value() {
+        assert!(Segment::Value(3).contains(&3));
+        assert!(!Segment::Value(3).contains(&4));
+        assert!(Segment::Range(-2, 2).contains(&0));
+        assert!(!Segment::Range(-2, 2).contains(&3));
+        assert!(Segment::SteppedRange(0u32, 10, 4).contains(&8));
+        assert!(!Segment::SteppedRange(0u32, 10, 4).contains(&10));
+        assert!(Segment::SteppedRange(0.0, 1.0, 0.25).contains(&0.75));
+        assert!(Segment::GeometricRange(1u32, 1000, 10).contains(&100));
+        assert!(!Segment::GeometricRange(1u32, 1000, 10).contains(&50));
+        assert!(!Segment::GeometricRange(1u32, 1000, 10).contains(&10000));
+    }
+}
@@ -0,0 +1,785 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, RangeInclusive, Sub};
+use std::str::FromStr;
+
+use crate::parser::check_countable;
+use crate::segment::{Segment, Selection};
+use crate::{Limits, RangeIter, RangeParser, RangeResult, Unit};
+
+/// A set of values stored as sorted, non-overlapping and non-adjacent inclusive intervals.
+///
+/// Contrary to [`crate::parse`], the values are never expanded, so the set is compact even for huge ranges
+/// and duplicated values are stored only once.
+///
+/// The set is meant for types where every value between two bounds can be reached by adding units to the start
+/// (e.g. integers).
+///
+/// # Example
+///
+/// ```rust
+/// use range_parser::RangeSet;
+///
+/// let set: RangeSet<u64> = RangeSet::parse("1,3-5,2,8").unwrap();
+/// assert_eq!(set.intervals(), &[1..=5, 8..=8]);
+/// assert!(set.contains(&4));
+/// assert!(!set.contains(&6));
+/// assert_eq!(set.len(), 6);
+/// assert_eq!(set.min(), Some(1));
+/// assert_eq!(set.max(), Some(8));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    intervals: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T> RangeSet<T>
+where
+    T: PartialEq + PartialOrd + Unit + Clone,
+{
+    /// Create a new empty [`RangeSet`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a range string into a [`RangeSet`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let set = range_parser::RangeSet::<i32>::parse("-3--1,0,5-6").unwrap();
+    /// assert_eq!(set.intervals(), &[-3..=0, 5..=6]);
+    /// ```
+    pub fn parse(range_str: &str) -> RangeResult<Self>
+    where
+        T: FromStr,
+    {
+        Self::parse_with(range_str, ",", "-")
+    }
+
+    /// Parse a range string into a [`RangeSet`] with custom separators
+    ///
+    /// See [`crate::parse_with`] for the rules about separators.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let set = range_parser::RangeSet::<u32>::parse_with("7;0..3;2", ";", "..").unwrap();
+    /// assert_eq!(set.intervals(), &[0..=3, 7..=7]);
+    /// ```
+    pub fn parse_with(
+        range_str: &str,
+        value_separator: &str,
+        range_separator: &str,
+    ) -> RangeResult<Self>
+    where
+        T: FromStr,
+    {
+        Self::parse_limited_with(
+            range_str,
+            value_separator,
+            range_separator,
+            &Limits::default(),
+        )
+    }
+
+    /// Parse a range string into a [`RangeSet`], enforcing the provided [`Limits`]
+    ///
+    /// The limits are checked before converting any value, as for [`crate::parse_limited`]: the values of stepped
+    /// and geometric ranges are stored as one interval each.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::{Limits, RangeError, RangeSet};
+    ///
+    /// let limits = Limits::new().max_items(1_000);
+    /// let set = RangeSet::<u64>::parse_limited("0-999,!0-40000000:2", &limits).unwrap();
+    /// assert_eq!(set.len(), 500);
+    /// assert_eq!(
+    ///     RangeSet::<u64>::parse_limited("0-40000000:2", &limits).unwrap_err(),
+    ///     RangeError::TooLarge(20000001)
+    /// );
+    /// ```
+    pub fn parse_limited(range_str: &str, limits: &Limits) -> RangeResult<Self>
+    where
+        T: FromStr,
+    {
+        Self::parse_limited_with(range_str, ",", "-", limits)
+    }
+
+    /// Parse a range string into a [`RangeSet`] with custom separators, enforcing the provided [`Limits`]
+    ///
+    /// See [`RangeSet::parse_limited`] for how limits are enforced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::{Limits, RangeError, RangeSet};
+    ///
+    /// let limits = Limits::new().max_segments(2);
+    /// assert_eq!(
+    ///     RangeSet::<u32>::parse_limited_with("1;2;3", ";", "..", &limits).unwrap_err(),
+    ///     RangeError::TooManySegments(3)
+    /// );
+    /// ```
+    pub fn parse_limited_with(
+        range_str: &str,
+        value_separator: &str,
+        range_separator: &str,
+        limits: &Limits,
+    ) -> RangeResult<Self>
+    where
+        T: FromStr,
+    {
+        let parser = RangeParser::with_separators(value_separator, range_separator).limits(*limits);
+        let selections = parser.selections(range_str, None)?;
+        limits.check_items(&selections)?;
+        // the values of stepped and geometric ranges are converted one by one
+        check_countable(selections.iter().filter_map(|selection| match selection {
+            Selection::Include(segment) | Selection::Exclude(segment)
+                if !segment.is_contiguous() =>
+            {
+                Some(segment)
+            }
+            _ => None,
+        }))?;
+        let mut intervals = Vec::new();
+        for selection in selections {
+            match selection {
+                Selection::Include(segment) => intervals.extend(segment_intervals(segment)),
+                // exclusions only remove the values accumulated so far
+                Selection::Exclude(segment) => {
+                    let set = Self::from_intervals(intervals);
+                    let excluded = Self::from_intervals(excluded_intervals(&set, segment));
+                    intervals = set.difference(&excluded).intervals;
+                }
+            }
+        }
+
+        Ok(Self::from_intervals(intervals))
+    }
+
+    /// Create a [`RangeSet`] from any list of inclusive intervals, which are normalized.
+    ///
+    /// Empty intervals (where start is bigger than end) are ignored.
+    pub fn from_intervals<I>(intervals: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        let mut intervals: Vec<RangeInclusive<T>> = intervals
+            .into_iter()
+            .filter(|interval| interval.start() <= interval.end())
+            .collect();
+        intervals.sort_by(|a, b| {
+            a.start()
+                .partial_cmp(b.start())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut normalized: Vec<RangeInclusive<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match normalized.last_mut() {
+                Some(last) if is_mergeable(last, &interval) => {
+                    if interval.end() > last.end() {
+                        *last = last.start().clone()..=interval.end().clone();
+                    }
+                }
+                _ => normalized.push(interval),
+            }
+        }
+
+        Self {
+            intervals: normalized,
+        }
+    }
+
+    /// Returns the normalized intervals of the set, sorted by their start
+    pub fn intervals(&self) -> &[RangeInclusive<T>] {
+        &self.intervals
+    }
+
+    /// Returns whether the set contains `value`.
+    ///
+    /// The lookup is performed with a binary search over the intervals.
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end() < value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Returns the amount of values in the set, without expanding it.
+    ///
+    /// Saturates to `usize::MAX` if the amount doesn't fit in a `usize`.
+    pub fn len(&self) -> usize {
+        self.intervals
+            .iter()
+            .map(|interval| {
+                T::steps_between(interval.start(), interval.end())
+                    .and_then(|steps| steps.checked_add(1))
+                    .unwrap_or(usize::MAX)
+            })
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Returns whether the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.intervals
+            .first()
+            .map(|interval| interval.start().clone())
+    }
+
+    /// Returns the biggest value in the set
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|interval| interval.end().clone())
+    }
+
+    /// Returns a lazy iterator over the values of the set, in ascending order
+    pub fn iter(&self) -> RangeIter<T> {
+        RangeIter::new(
+            self.intervals
+                .iter()
+                .map(|interval| Segment::Range(interval.start().clone(), interval.end().clone()))
+                .collect(),
+        )
+    }
+
+    /// Returns the set of values which are in `self` or in `other`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::RangeSet;
+    ///
+    /// let a: RangeSet<u32> = RangeSet::parse("1-5,10").unwrap();
+    /// let b: RangeSet<u32> = RangeSet::parse("4-8").unwrap();
+    /// assert_eq!(a.union(&b).intervals(), &[1..=8, 10..=10]);
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_intervals(self.intervals.iter().chain(other.intervals.iter()).cloned())
+    }
+
+    /// Returns the set of values which are both in `self` and in `other`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::RangeSet;
+    ///
+    /// let a: RangeSet<u32> = RangeSet::parse("1-5,10").unwrap();
+    /// let b: RangeSet<u32> = RangeSet::parse("4-8,10-12").unwrap();
+    /// assert_eq!(a.intersection(&b).intervals(), &[4..=5, 10..=10]);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut a = self.intervals.iter().peekable();
+        let mut b = other.intervals.iter().peekable();
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = max(x.start(), y.start()).clone();
+            let end = min(x.end(), y.end()).clone();
+            if start <= end {
+                intervals.push(start..=end);
+            }
+            // advance the interval which ends first
+            if x.end() < y.end() {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the set of values which are in `self`, but not in `other`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::RangeSet;
+    ///
+    /// let requested: RangeSet<u32> = RangeSet::parse("1-10").unwrap();
+    /// let reserved: RangeSet<u32> = RangeSet::parse("3,6-7").unwrap();
+    /// assert_eq!(requested.difference(&reserved).intervals(), &[1..=2, 4..=5, 8..=10]);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+
+        for interval in &self.intervals {
+            let end = interval.end();
+            let mut start = Some(interval.start().clone());
+            let first = other
+                .intervals
+                .partition_point(|excluded| excluded.end() < interval.start());
+
+            for excluded in &other.intervals[first..] {
+                let Some(current) = start.as_ref().filter(|current| *current <= end) else {
+                    break;
+                };
+                if excluded.start() > end {
+                    break;
+                }
+                if excluded.start() > current {
+                    // the excluded interval starts after current, so its predecessor is at least current
+                    if let Some(before) = excluded.start().backward_checked(1) {
+                        intervals.push(current.clone()..=before);
+                    }
+                }
+                start = excluded.end().forward_checked(1);
+            }
+
+            if let Some(start) = start.filter(|start| start <= end) {
+                intervals.push(start..=end.clone());
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns the set of values which are either in `self` or in `other`, but not in both
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::RangeSet;
+    ///
+    /// let a: RangeSet<u32> = RangeSet::parse("1-5").unwrap();
+    /// let b: RangeSet<u32> = RangeSet::parse("4-8").unwrap();
+    /// assert_eq!(a.symmetric_difference(&b).intervals(), &[1..=3, 6..=8]);
+    /// ```
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.union(other).difference(&self.intersection(other))
+    }
+
+    /// Returns the set of values of `universe` which are not in `self`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use range_parser::RangeSet;
+    ///
+    /// let set: RangeSet<u8> = RangeSet::parse("0-3,8-11").unwrap();
+    /// assert_eq!(set.complement(0..=15).intervals(), &[4..=7, 12..=15]);
+    /// ```
+    pub fn complement(&self, universe: RangeInclusive<T>) -> Self {
+        Self::from_intervals([universe]).difference(self)
+    }
+
+    /// Returns whether all the values of `self` are also in `other`
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.intervals.iter().all(|interval| {
+            let index = other
+                .intervals
+                .partition_point(|candidate| candidate.end() < interval.start());
+            other.intervals.get(index).is_some_and(|candidate| {
+                candidate.start() <= interval.start() && interval.end() <= candidate.end()
+            })
+        })
+    }
+
+    /// Returns whether all the values of `other` are also in `self`
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns whether `self` and `other` have no values in common
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+/// Returns the intervals of the values of a segment
+fn segment_intervals<T>(segment: Segment<T>) -> Vec<RangeInclusive<T>>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    match segment {
+        Segment::Value(value) => vec![value.clone()..=value],
+        Segment::Range(start, end) => vec![start..=end],
+        Segment::SteppedRange(start, end, step) if step == T::unit() => vec![start..=end],
+        // values of stepped and geometric ranges are not contiguous
+        segment @ (Segment::SteppedRange(..) | Segment::GeometricRange(..)) => {
+            RangeIter::new(vec![segment])
+                .map(|value| value.clone()..=value)
+                .collect()
+        }
+    }
+}
+
+/// Convert the values of an excluded segment which are in `set` to inclusive intervals, without expanding the
+/// values outside of it
+fn excluded_intervals<T>(set: &RangeSet<T>, segment: Segment<T>) -> Vec<RangeInclusive<T>>
+where
+    T: PartialOrd + Unit + Clone,
+{
+    if segment.is_contiguous() {
+        return segment_intervals(segment);
+    }
+    set.intervals
+        .iter()
+        .flat_map(|interval| {
+            RangeIter::from_value(segment.clone(), interval.start())
+                .take_while(|value| value <= interval.end())
+                .map(|value| value.clone()..=value)
+        })
+        .collect()
+}
+
+/// Returns the smallest between `a` and `b`
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Returns the biggest between `a` and `b`
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Returns whether `next`, which doesn't start before `last`, overlaps or is adjacent to `last`
+fn is_mergeable<T>(last: &RangeInclusive<T>, next: &RangeInclusive<T>) -> bool
+where
+    T: PartialOrd + Unit,
+{
+    next.start() <= last.end()
+        || last
+            .end()
+            .forward_checked(1)
+            .is_some_and(|after_end| *next.start() <= after_end)
+}
+
+impl<T> FromStr for RangeSet<T>
+where
+    T: FromStr + PartialEq + PartialOrd + Unit + Clone,
+{
+    type Err = crate::RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Formats the set as a range string, which can be parsed back with [`RangeSet::parse`]
+impl<T> fmt::Display for RangeSet<T>
+where
+    T: fmt::Display + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, interval) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            if interval.start() == interval.end() {
+                write!(f, "{}", interval.start())?;
+            } else {
+                write!(f, "{}-{}", interval.start(), interval.end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> FromIterator<T> for RangeSet<T>
+where
+    T: PartialEq + PartialOrd + Unit + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_intervals(iter.into_iter().map(|value| value.clone()..=value))
+    }
+}
+
+impl<T> IntoIterator for &RangeSet<T>
+where
+    T: PartialEq + PartialOrd + Unit + Clone,
+{
+    type Item = T;
+    type IntoIter = RangeIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+macro_rules! impl_set_operator {
+    ($($trait:ident :: $method:ident => $operation:ident),*) => ($(
+        impl<T> $trait<&RangeSet<T>> for &RangeSet<T>
+        where
+            T: PartialEq + PartialOrd + Unit + Clone,
+        {
+            type Output = RangeSet<T>;
+
+            fn $method(self, rhs: &RangeSet<T>) -> Self::Output {
+                self.$operation(rhs)
+            }
+        }
+    )*)
+}
+
+impl_set_operator!(
+    BitOr::bitor => union,
+    BitAnd::bitand => intersection,
+    Sub::sub => difference,
+    BitXor::bitxor => symmetric_difference
+);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::RangeError;
+
+    #[test]
+    fn should_parse_normalized_intervals() {
+        let set: RangeSet<u64> = RangeSet::parse("1,3-5,2").unwrap();
+        assert_eq!(set.intervals(), &[1..=5]);
+    }
+
+    #[test]
+    fn should_merge_overlapping_and_sort_intervals() {
+        let set: RangeSet<i32> = RangeSet::parse("10-20,-5--1,15-25,30,0").unwrap();
+        assert_eq!(set.intervals(), &[-5..=0, 10..=25, 30..=30]);
+    }
+
+    #[test]
+    fn should_store_duplicates_once() {
+        let set: RangeSet<u8> = RangeSet::parse("3,3,3").unwrap();
+        assert_eq!(set.intervals(), &[3..=3]);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn should_parse_with_custom_separators() {
+        let set: RangeSet<u32> = "0-1".parse().unwrap();
+        assert_eq!(set.intervals(), &[0..=1]);
+        let set: RangeSet<u32> = RangeSet::parse_with("8;0..3;4", ";", "..").unwrap();
+        assert_eq!(set.intervals(), &[0..=4, 8..=8]);
+    }
+
+    #[test]
+    fn should_tell_whether_set_contains_value() {
+        let set: RangeSet<u64> = RangeSet::parse("0-3,8-11,20").unwrap();
+        assert!(set.contains(&0));
+        assert!(set.contains(&3));
+        assert!(set.contains(&9));
+        assert!(set.contains(&20));
+        assert!(!set.contains(&4));
+        assert!(!set.contains(&12));
+        assert!(!set.contains(&21));
+    }
+
+    #[test]
+    fn should_get_len_without_expanding() {
+        let set: RangeSet<u64> = RangeSet::parse("0-4000000000,5").unwrap();
+        assert_eq!(set.len(), 4_000_000_001);
+        assert!(!set.is_empty());
+        assert!(RangeSet::<u64>::new().is_empty());
+        assert_eq!(RangeSet::<u64>::new().len(), 0);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn should_saturate_len() {
+        let set: RangeSet<u64> = RangeSet::parse("0-18446744073709551615").unwrap();
+        assert_eq!(set.len(), usize::MAX);
+    }
+
+    #[test]
+    fn should_get_min_and_max() {
+        let set: RangeSet<i32> = RangeSet::parse("4,-2-0,9").unwrap();
+        assert_eq!(set.min(), Some(-2));
+        assert_eq!(set.max(), Some(9));
+        assert_eq!(RangeSet::<i32>::new().min(), None);
+        assert_eq!(RangeSet::<i32>::new().max(), None);
+    }
+
+    #[test]
+    fn should_iterate_over_values() {
+        let set: RangeSet<u8> = RangeSet::parse("5,1-2,254-255").unwrap();
+        assert_eq!(set.iter().collect::<Vec<u8>>(), vec![1, 2, 5, 254, 255]);
+        assert_eq!((&set).into_iter().next_back(), Some(255));
+    }
+
+    #[test]
+    fn should_collect_values_into_set() {
+        let set: RangeSet<u8> = [5, 1, 2, 3, 255, 254].into_iter().collect();
+        assert_eq!(set.intervals(), &[1..=3, 5..=5, 254..=255]);
+    }
+
+    #[test]
+    fn should_ignore_empty_intervals() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let set = RangeSet::from_intervals([5..=1, 2..=3]);
+        assert_eq!(set.intervals(), &[2..=3]);
+    }
+
+    fn set(range_str: &str) -> RangeSet<i32> {
+        RangeSet::parse(range_str).unwrap()
+    }
+
+    #[test]
+    fn should_compute_union() {
+        assert_eq!(
+            set("1-5,10").union(&set("4-8")).intervals(),
+            &[1..=8, 10..=10]
+        );
+        assert_eq!(set("1-3").union(&set("4-6")).intervals(), &[1..=6]);
+        assert_eq!(set("1-3").union(&RangeSet::new()), set("1-3"));
+        assert_eq!(&set("1") | &set("3"), set("1,3"));
+    }
+
+    #[test]
+    fn should_compute_intersection() {
+        assert_eq!(
+            set("1-5,10,20-30")
+                .intersection(&set("4-8,10-12,25"))
+                .intervals(),
+            &[4..=5, 10..=10, 25..=25]
+        );
+        assert!(set("1-3").intersection(&set("4-6")).is_empty());
+        assert_eq!(&set("-5-5") & &set("0-10"), set("0-5"));
+    }
+
+    #[test]
+    fn should_compute_difference() {
+        assert_eq!(
+            set("1-10,20-25")
+                .difference(&set("3,6-7,10-21"))
+                .intervals(),
+            &[1..=2, 4..=5, 8..=9, 22..=25]
+        );
+        assert_eq!(set("1-10").difference(&set("0-20")), RangeSet::new());
+        assert_eq!(set("1-10").difference(&set("20-30")), set("1-10"));
+        assert_eq!(&set("1-10") - &set("1,10"), set("2-9"));
+    }
+
+    #[test]
+    fn should_compute_difference_at_type_bounds() {
+        let all: RangeSet<u8> = RangeSet::parse("0-255").unwrap();
+        let edges: RangeSet<u8> = RangeSet::parse("0,255").unwrap();
+        assert_eq!(all.difference(&edges).intervals(), &[1..=254]);
+        assert_eq!(edges.difference(&all), RangeSet::new());
+    }
+
+    #[test]
+    fn should_compute_symmetric_difference() {
+        assert_eq!(
+            set("1-5,10").symmetric_difference(&set("4-8")).intervals(),
+            &[1..=3, 6..=8, 10..=10]
+        );
+        assert_eq!(&set("1-5") ^ &set("1-5"), RangeSet::new());
+    }
+
+    #[test]
+    fn should_compute_complement() {
+        let cpus: RangeSet<u8> = RangeSet::parse("0-3,8-11").unwrap();
+        assert_eq!(cpus.complement(0..=15).intervals(), &[4..=7, 12..=15]);
+        assert_eq!(cpus.complement(2..=9).intervals(), &[4..=7]);
+        assert_eq!(
+            RangeSet::<u8>::new().complement(0..=255).intervals(),
+            &[0..=255]
+        );
+    }
+
+    #[test]
+    fn should_compare_sets() {
+        assert!(set("2-3,8").is_subset(&set("1-5,7-9")));
+        assert!(set("2-6").is_subset(&set("1-5,6-9,12")));
+        assert!(!set("2-6").is_subset(&set("1-5,7-9")));
+        assert!(!set("4-8").is_subset(&set("1-5,7-9")));
+        assert!(RangeSet::new().is_subset(&set("1")));
+        assert!(set("1-5,7-9").is_superset(&set("2-3,8")));
+        assert!(set("1-3").is_disjoint(&set("4-6")));
+        assert!(!set("1-4").is_disjoint(&set("4-6")));
+    }
+
+    #[test]
+    fn should_display_set() {
+        assert_eq!(set("3,1-2,-4--2,7").to_string(), "-4--2,1-3,7");
+        assert_eq!(RangeSet::<u8>::new().to_string(), "");
+        let cpus: RangeSet<u8> = "0-3,8-11".parse().unwrap();
+        assert_eq!(cpus.to_string().parse::<RangeSet<u8>>().unwrap(), cpus);
+    }
+
+    #[test]
+    fn should_parse_stepped_ranges() {
+        assert_eq!(set("0-10:4,1-2").intervals(), &[0..=2, 4..=4, 8..=8]);
+        assert_eq!(set("0-5:1").intervals(), &[0..=5]);
+    }
+
+    #[test]
+    fn should_merge_char_intervals() {
+        let chars: RangeSet<char> = RangeSet::parse("a-c,d-f,x").unwrap();
+        assert_eq!(chars.intervals(), &['a'..='f', 'x'..='x']);
+        assert_eq!(chars.to_string(), "a-f,x");
+    }
+
+    #[test]
+    fn should_exclude_values() {
+        assert_eq!(set("1-20,!5,!10-12").intervals(), &[1..=4, 6..=9, 13..=20]);
+        assert_eq!(set("!2,1-3").intervals(), &[1..=3]);
+        assert_eq!(set("0-10,!0-10:2").intervals().len(), 5);
+    }
+
+    #[test]
+    fn should_exclude_stepped_ranges_without_expanding_them() {
+        let set = RangeSet::<u64>::parse("0-99,200-209,!1-4000000000:2").unwrap();
+        assert_eq!(set.len(), 55);
+        assert_eq!(set.intervals()[..2], [0..=0, 2..=2]);
+        assert_eq!(set.max(), Some(208));
+        assert_eq!(
+            RangeSet::<u64>::parse("0-20,!2-4000000000*2")
+                .unwrap()
+                .intervals(),
+            &[0..=1, 3..=3, 5..=7, 9..=15, 17..=20]
+        );
+    }
+
+    #[test]
+    fn should_apply_limits_before_converting_values() {
+        let limits = Limits::new().max_items(100);
+        assert_eq!(
+            RangeSet::<u64>::parse_limited("0-40000000:2", &limits).unwrap_err(),
+            RangeError::TooLarge(20000001)
+        );
+        assert_eq!(
+            RangeSet::<u64>::parse_limited("0-9,!0-60000000:2", &limits)
+                .unwrap()
+                .intervals(),
+            &[1..=1, 3..=3, 5..=5, 7..=7, 9..=9]
+        );
+        assert_eq!(
+            RangeSet::<u32>::parse_limited_with("1;2;3", ";", "-", &Limits::new().max_segments(2))
+                .unwrap_err(),
+            RangeError::TooManySegments(3)
+        );
+    }
+
+    #[test]
+    fn should_fail_on_invalid_range() {
+        assert!(RangeSet::<u64>::parse("1-x").is_err());
+    }
+}
@@ -1,8 +1,108 @@
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
+};
+
 /// A trait for types that have a unit value.
 ///
 /// E.g. 1 for integers, 1.0 for floats, etc.
-pub trait Unit {
+pub trait Unit: Sized {
+    /// Returns the unit value for the type
     fn unit() -> Self;
+
+    /// Returns the number of `step`s required to go from `start` to the last value which is not bigger than `end`.
+    ///
+    /// Returns `None` if `start` is bigger than `end`, if `step` is not positive
+    /// or if the amount of steps doesn't fit in a `usize`.
+    fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize>;
+
+    /// Returns the value obtained by adding `count` times `step` to `self`.
+    ///
+    /// This is the checked successor used to step through ranges, so it must return `None` if the result can't be
+    /// represented by the type (e.g. it would overflow), instead of panicking or wrapping around.
+    fn forward_by(&self, step: &Self, count: usize) -> Option<Self>;
+
+    /// Returns the value obtained by subtracting `count` units from `self`.
+    ///
+    /// This is the checked predecessor, so it must return `None` if the result can't be represented by the type.
+    fn backward_checked(&self, count: usize) -> Option<Self>;
+
+    /// Returns the number of unit steps required to go from `start` to `end`.
+    ///
+    /// Returns `None` if `start` is bigger than `end` or if the amount of steps doesn't fit in a `usize`.
+    fn steps_between(start: &Self, end: &Self) -> Option<usize> {
+        Self::steps_between_by(start, end, &Self::unit())
+    }
+
+    /// Returns the value obtained by adding `count` units to `self`.
+    ///
+    /// Returns `None` if the result can't be represented by the type.
+    fn forward_checked(&self, count: usize) -> Option<Self> {
+        self.forward_by(&Self::unit(), count)
+    }
+
+    /// Returns the step which divides the range from `start` to `end` into `count` equal steps, so that `end` is
+    /// reached by adding `count` times the step to `start`.
+    ///
+    /// Returns `None` if the range can't be divided evenly (e.g. `0-10` in 3 steps for integers), which is the
+    /// default.
+    fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+        let _ = (start, end, count);
+        None
+    }
+
+    /// Returns the value obtained by multiplying `self` by `factor` raised to the power of `count`.
+    ///
+    /// This is the checked successor used to step through geometric ranges (e.g. `1-1000*10`), so it must return
+    /// `None` if the result can't be represented by the type. Returns `None` by default, so geometric ranges are
+    /// not supported.
+    fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+        let _ = (factor, count);
+        None
+    }
+
+    /// Returns how many times `start` can be multiplied by `factor` without exceeding `end`.
+    ///
+    /// Returns `None` if `start` is bigger than `end`, if `start` is not positive, if `factor` is not bigger than
+    /// one or if geometric ranges are not supported, which is the default.
+    fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+        let _ = (start, end, factor);
+        None
+    }
+
+    /// Returns the factor which divides the range from `start` to `end` into `count` equal ratios, so that `end`
+    /// is reached by multiplying `start` by the factor `count` times.
+    ///
+    /// Returns `None` if the range can't be divided evenly (e.g. `1-10` in 2 ratios for integers), which is the
+    /// default.
+    fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+        let _ = (start, end, count);
+        None
+    }
+
+    /// Returns whether `value`, which couldn't be parsed as `Self`, is zero for a type which can't represent zero
+    /// (e.g. [`NonZeroU32`]), so that it's reported as [`crate::RangeError::ZeroValue`] instead of
+    /// [`crate::RangeError::NotANumber`].
+    ///
+    /// Returns `false` by default.
+    fn is_forbidden_zero(value: &str) -> bool {
+        let _ = value;
+        false
+    }
+}
+
+/// Returns how many times `start` can be scaled by `scale` without exceeding `end`
+pub(crate) fn count_scales<T>(start: &T, end: &T, scale: impl Fn(&T) -> Option<T>) -> usize
+where
+    T: PartialOrd,
+{
+    let mut count = 0;
+    let mut value = scale(start);
+    while let Some(current) = value.filter(|value| value <= end) {
+        count += 1;
+        value = scale(&current);
+    }
+    count
 }
 
 /// Implement One for common numeric types.
@@ -12,12 +112,307 @@ macro_rules! impl_one_for_numeric {
             fn unit() -> Self {
                 1
             }
+
+            fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+                if start > end || *step <= 0 {
+                    return None;
+                }
+                usize::try_from((*end as i128 - *start as i128) / *step as i128).ok()
+            }
+
+            fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+                (*step as i128)
+                    .checked_mul(count as i128)
+                    .and_then(|offset| offset.checked_add(*self as i128))
+                    .and_then(|value| Self::try_from(value).ok())
+            }
+
+            fn backward_checked(&self, count: usize) -> Option<Self> {
+                Self::try_from(*self as i128 - count as i128).ok()
+            }
+
+            fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                let distance = *end as i128 - *start as i128;
+                let count = i128::try_from(count).ok().filter(|count| *count > 0)?;
+                if distance % count != 0 {
+                    return None;
+                }
+                Self::try_from(distance / count).ok()
+            }
+
+            fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+                (*factor as i128)
+                    .checked_pow(u32::try_from(count).ok()?)
+                    .and_then(|scale| scale.checked_mul(*self as i128))
+                    .and_then(|value| Self::try_from(value).ok())
+            }
+
+            fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+                if start > end || *start <= 0 || *factor <= 1 {
+                    return None;
+                }
+                Some(count_scales(start, end, |value| value.scale_by(factor, 1)))
+            }
+
+            fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                if start > end || *start <= 0 || count == 0 {
+                    return None;
+                }
+                // the root is estimated with floats, then checked on the candidates around it
+                let estimate = (*end as f64 / *start as f64).powf(1.0 / count as f64).round() as Self;
+                [estimate.saturating_sub(1), estimate, estimate.saturating_add(1)]
+                    .into_iter()
+                    .find(|factor| start.scale_by(factor, count) == Some(*end))
+            }
         }
     )*)
 }
 
 impl_one_for_numeric!(usize u8 u16 u32 u64 isize i8 i16 i32 i64);
 
+/// Implement One for 128-bit integers, which don't fit in a wider type.
+macro_rules! impl_one_for_wide_numeric {
+    ($($t:ty)*) => ($(
+        impl Unit for $t {
+            fn unit() -> Self {
+                1
+            }
+
+            fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+                if start > end || *step <= 0 {
+                    return None;
+                }
+                usize::try_from(end.abs_diff(*start) / step.abs_diff(0)).ok()
+            }
+
+            fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+                step.checked_mul(Self::try_from(count).ok()?)
+                    .and_then(|offset| self.checked_add(offset))
+            }
+
+            fn backward_checked(&self, count: usize) -> Option<Self> {
+                self.checked_sub(Self::try_from(count).ok()?)
+            }
+
+            fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                if start > end {
+                    return None;
+                }
+                let distance = end.abs_diff(*start);
+                let count = u128::try_from(count).ok().filter(|count| *count > 0)?;
+                if distance % count != 0 {
+                    return None;
+                }
+                Self::try_from(distance / count).ok()
+            }
+
+            fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+                factor
+                    .checked_pow(u32::try_from(count).ok()?)
+                    .and_then(|scale| scale.checked_mul(*self))
+            }
+
+            fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+                if start > end || *start <= 0 || *factor <= 1 {
+                    return None;
+                }
+                Some(count_scales(start, end, |value| value.scale_by(factor, 1)))
+            }
+
+            fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                if start > end || *start <= 0 || count == 0 {
+                    return None;
+                }
+                // the root is estimated with floats, then checked on the candidates around it
+                let estimate = (*end as f64 / *start as f64).powf(1.0 / count as f64).round() as Self;
+                [estimate.saturating_sub(1), estimate, estimate.saturating_add(1)]
+                    .into_iter()
+                    .find(|factor| start.scale_by(factor, count) == Some(*end))
+            }
+        }
+    )*)
+}
+
+impl_one_for_wide_numeric!(u128 i128);
+
+/// Implement One for non-zero unsigned integers, stepping as their primitive type.
+macro_rules! impl_one_for_non_zero {
+    ($($t:ty => $p:ty)*) => ($(
+        impl Unit for $t {
+            fn unit() -> Self {
+                Self::MIN
+            }
+
+            fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+                <$p>::steps_between_by(&start.get(), &end.get(), &step.get())
+            }
+
+            fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+                self.get().forward_by(&step.get(), count).and_then(Self::new)
+            }
+
+            fn backward_checked(&self, count: usize) -> Option<Self> {
+                self.get().backward_checked(count).and_then(Self::new)
+            }
+
+            fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                <$p>::step_dividing(&start.get(), &end.get(), count).and_then(Self::new)
+            }
+
+            fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+                self.get().scale_by(&factor.get(), count).and_then(Self::new)
+            }
+
+            fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+                <$p>::scales_between_by(&start.get(), &end.get(), &factor.get())
+            }
+
+            fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                <$p>::factor_dividing(&start.get(), &end.get(), count).and_then(Self::new)
+            }
+
+            fn is_forbidden_zero(value: &str) -> bool {
+                value.trim().parse::<$p>().is_ok_and(|value| value == 0)
+            }
+        }
+    )*)
+}
+
+impl_one_for_non_zero!(
+    NonZeroUsize => usize
+    NonZeroU8 => u8
+    NonZeroU16 => u16
+    NonZeroU32 => u32
+    NonZeroU64 => u64
+    NonZeroU128 => u128
+);
+
+/// Implement One for non-zero signed integers, stepping as their primitive type but skipping zero, as chars skip
+/// the surrogate gap.
+macro_rules! impl_one_for_signed_non_zero {
+    ($($t:ty => $p:ty)*) => ($(
+        impl Unit for $t {
+            fn unit() -> Self {
+                Self::new(1).expect("one is not zero")
+            }
+
+            fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+                let steps = <$p>::steps_between_by(&start.get(), &end.get(), &step.get())?;
+                // zero is one of the steps of the primitive type if the range crosses it
+                let crosses_zero = start.get() < 0
+                    && end.get() > 0
+                    && start.get().unsigned_abs() % step.get().unsigned_abs() == 0;
+                Some(steps - usize::from(crosses_zero))
+            }
+
+            fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+                let value = self.get().forward_by(&step.get(), count)?;
+                // a positive step which would have landed on zero takes one more step
+                let skips_zero = self.get() < 0
+                    && step.get() > 0
+                    && value >= 0
+                    && self.get().unsigned_abs() % step.get().unsigned_abs() == 0;
+                if skips_zero {
+                    value.forward_by(&step.get(), 1).and_then(Self::new)
+                } else {
+                    Self::new(value)
+                }
+            }
+
+            fn backward_checked(&self, count: usize) -> Option<Self> {
+                let value = self.get().backward_checked(count)?;
+                if self.get() > 0 && value <= 0 {
+                    value.backward_checked(1).and_then(Self::new)
+                } else {
+                    Self::new(value)
+                }
+            }
+
+            fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                // the range has one more step in the primitive type if it crosses zero
+                [count, count + 1]
+                    .into_iter()
+                    .filter_map(|steps| <$p>::step_dividing(&start.get(), &end.get(), steps))
+                    .filter_map(Self::new)
+                    .find(|step| {
+                        Self::steps_between_by(start, end, step) == Some(count)
+                            && start.forward_by(step, count) == Some(*end)
+                    })
+            }
+
+            fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+                self.get().scale_by(&factor.get(), count).and_then(Self::new)
+            }
+
+            fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+                <$p>::scales_between_by(&start.get(), &end.get(), &factor.get())
+            }
+
+            fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                <$p>::factor_dividing(&start.get(), &end.get(), count).and_then(Self::new)
+            }
+
+            fn is_forbidden_zero(value: &str) -> bool {
+                value.trim().parse::<$p>().is_ok_and(|value| value == 0)
+            }
+        }
+    )*)
+}
+
+impl_one_for_signed_non_zero!(
+    NonZeroIsize => isize
+    NonZeroI8 => i8
+    NonZeroI16 => i16
+    NonZeroI32 => i32
+    NonZeroI64 => i64
+    NonZeroI128 => i128
+);
+
+/// Implement One for wrappers of integers, which step without wrapping or saturating, so that ranges end at the
+/// bounds of the type.
+macro_rules! impl_one_for_wrapper {
+    ($($w:ident)*) => ($(
+        impl<T> Unit for $w<T>
+        where
+            T: Unit,
+        {
+            fn unit() -> Self {
+                $w(T::unit())
+            }
+
+            fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+                T::steps_between_by(&start.0, &end.0, &step.0)
+            }
+
+            fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+                self.0.forward_by(&step.0, count).map($w)
+            }
+
+            fn backward_checked(&self, count: usize) -> Option<Self> {
+                self.0.backward_checked(count).map($w)
+            }
+
+            fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                T::step_dividing(&start.0, &end.0, count).map($w)
+            }
+
+            fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+                self.0.scale_by(&factor.0, count).map($w)
+            }
+
+            fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+                T::scales_between_by(&start.0, &end.0, &factor.0)
+            }
+
+            fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                T::factor_dividing(&start.0, &end.0, count).map($w)
+            }
+        }
+    )*)
+}
+
+impl_one_for_wrapper!(Wrapping Saturating);
+
 /// Implement One for common float types.
 macro_rules! impl_one_for_floats {
     ($($t:ty)*) => ($(
@@ -25,8 +420,402 @@ macro_rules! impl_one_for_floats {
             fn unit() -> Self {
                 1.0
             }
+
+            fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+                if *step <= 0.0 {
+                    return None;
+                }
+                // the quotient may be rounded just below the exact number of steps (e.g. `0.3 / 0.1` is
+                // `2.9999999999999996`), so that a reachable end would be dropped: round it to the nearest integer
+                // when the difference is within a few ULPs of the quotient
+                let quotient = (end - start) / step;
+                let nearest = quotient.round();
+                let tolerance = nearest.abs() * <$t>::EPSILON * 4.0;
+                let steps = if (quotient - nearest).abs() <= tolerance {
+                    nearest
+                } else {
+                    quotient.floor()
+                };
+                if steps.is_finite() && steps >= 0.0 && steps < usize::MAX as $t {
+                    Some(steps as usize)
+                } else {
+                    None
+                }
+            }
+
+            fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+                let value = self + step * count as $t;
+                // when the float is too big, adding a step doesn't change its value
+                (value.is_finite() && (count == 0 || value != *self)).then_some(value)
+            }
+
+            fn backward_checked(&self, count: usize) -> Option<Self> {
+                let value = self - count as $t;
+                (value.is_finite() && (count == 0 || value < *self)).then_some(value)
+            }
+
+            fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                let mut step = (end - start) / count as $t;
+                // the quotient may be rounded up, so that the range would stop before its end: shrink the step by
+                // the smallest amount until `end` is reached by adding `count` times the step to `start`
+                while step > 0.0
+                    && step.is_finite()
+                    && (start + step * count as $t > *end
+                        || Self::steps_between_by(start, end, &step).is_none_or(|steps| steps < count))
+                {
+                    step = <$t>::from_bits(step.to_bits() - 1);
+                }
+                step.is_finite().then_some(step)
+            }
+
+            fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+                let value = self * factor.powi(i32::try_from(count).ok()?);
+                (value.is_finite() && (count == 0 || value != *self)).then_some(value)
+            }
+
+            fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+                if start > end || *start <= 0.0 || *factor <= 1.0 {
+                    return None;
+                }
+                let estimate = ((end / start).ln() / factor.ln()).floor();
+                if !estimate.is_finite() || estimate < 0.0 || estimate >= i32::MAX as $t {
+                    return None;
+                }
+                // the logarithms are rounded, so the estimate may be off by one
+                let mut count = estimate as usize;
+                while start.scale_by(factor, count + 1).is_some_and(|value| value <= *end) {
+                    count += 1;
+                }
+                while count > 0 && start.scale_by(factor, count).is_none_or(|value| value > *end) {
+                    count -= 1;
+                }
+                Some(count)
+            }
+
+            fn factor_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+                if start > end || *start <= 0.0 {
+                    return None;
+                }
+                let mut factor = (end / start).powf(1.0 / count as $t);
+                // as for the step, shrink the factor until `end` is reached by multiplying `start` `count` times
+                while factor > 1.0
+                    && factor.is_finite()
+                    && (start.scale_by(&factor, count).is_none_or(|value| value > *end)
+                        || Self::scales_between_by(start, end, &factor).is_none_or(|scales| scales < count))
+                {
+                    factor = <$t>::from_bits(factor.to_bits() - 1);
+                }
+                factor.is_finite().then_some(factor)
+            }
         }
     )*)
 }
 
 impl_one_for_floats!(f32 f64);
+
+/// First scalar value of the surrogate gap, which contains no `char`
+const SURROGATE_START: u32 = 0xD800;
+/// Amount of scalar values in the surrogate gap
+const SURROGATE_LEN: u32 = 0x800;
+
+/// Returns the index of a `char` among all the chars, skipping the surrogate gap
+fn char_index(c: char) -> u32 {
+    match c as u32 {
+        scalar if scalar >= SURROGATE_START => scalar - SURROGATE_LEN,
+        scalar => scalar,
+    }
+}
+
+/// Returns the `char` at the index among all the chars, skipping the surrogate gap
+fn char_from_index(index: u64) -> Option<char> {
+    let index = u32::try_from(index).ok()?;
+    match index {
+        index if index >= SURROGATE_START => char::from_u32(index.checked_add(SURROGATE_LEN)?),
+        index => char::from_u32(index),
+    }
+}
+
+/// Chars step by Unicode scalar value, skipping the surrogate gap (e.g. `'\u{D7FF}'` is followed by `'\u{E000}'`).
+///
+/// The unit is `'\u{1}'`. Steps other than the unit are not supported, since they would be chars too.
+impl Unit for char {
+    fn unit() -> Self {
+        '\u{1}'
+    }
+
+    fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+        if start > end || *step != Self::unit() {
+            return None;
+        }
+        usize::try_from(char_index(*end) - char_index(*start)).ok()
+    }
+
+    fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+        let offset = u64::from(char_index(*step)).checked_mul(count as u64)?;
+        char_from_index(u64::from(char_index(*self)).checked_add(offset)?)
+    }
+
+    fn backward_checked(&self, count: usize) -> Option<Self> {
+        char_from_index(u64::from(char_index(*self)).checked_sub(count as u64)?)
+    }
+}
+
+/// Decimals step exactly, so fractional steps (e.g. `0.0-1.0:0.1`) don't accumulate rounding errors.
+///
+/// Requires the `rust_decimal` feature.
+#[cfg(feature = "rust_decimal")]
+impl Unit for rust_decimal::Decimal {
+    fn unit() -> Self {
+        Self::ONE
+    }
+
+    fn steps_between_by(start: &Self, end: &Self, step: &Self) -> Option<usize> {
+        if start > end || *step <= Self::ZERO {
+            return None;
+        }
+        usize::try_from(end.checked_sub(*start)?.checked_div(*step)?.floor()).ok()
+    }
+
+    fn forward_by(&self, step: &Self, count: usize) -> Option<Self> {
+        // keep the scale of the value (e.g. `0.0`), since multiplying by zero would drop it
+        if count == 0 {
+            return Some(*self);
+        }
+        step.checked_mul(Self::from(count))
+            .and_then(|offset| self.checked_add(offset))
+    }
+
+    fn backward_checked(&self, count: usize) -> Option<Self> {
+        self.checked_sub(Self::from(count))
+    }
+
+    fn step_dividing(start: &Self, end: &Self, count: usize) -> Option<Self> {
+        let count = Self::from(count);
+        let step = end.checked_sub(*start)?.checked_div(count)?;
+        // the quotient is rounded if it has too many digits (e.g. `1 / 3`)
+        (step.checked_mul(count)? == end.checked_sub(*start)?).then_some(step)
+    }
+
+    fn scale_by(&self, factor: &Self, count: usize) -> Option<Self> {
+        // exponentiation by squaring
+        let mut value = *self;
+        let mut base = *factor;
+        let mut exponent = count;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                value = value.checked_mul(base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Some(value)
+    }
+
+    fn scales_between_by(start: &Self, end: &Self, factor: &Self) -> Option<usize> {
+        if start > end || *start <= Self::ZERO || *factor <= Self::ONE {
+            return None;
+        }
+        Some(count_scales(start, end, |value| value.scale_by(factor, 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_step_chars() {
+        assert_eq!('a'.forward_checked(1), Some('b'));
+        assert_eq!('a'.forward_checked(25), Some('z'));
+        assert_eq!('b'.backward_checked(1), Some('a'));
+        assert_eq!('\0'.backward_checked(1), None);
+        assert_eq!(char::MAX.forward_checked(1), None);
+        assert_eq!(char::steps_between(&'a', &'z'), Some(25));
+        assert_eq!(char::steps_between(&'z', &'a'), None);
+    }
+
+    #[test]
+    fn should_skip_surrogate_gap() {
+        assert_eq!('\u{D7FF}'.forward_checked(1), Some('\u{E000}'));
+        assert_eq!('\u{E000}'.backward_checked(1), Some('\u{D7FF}'));
+        assert_eq!(char::steps_between(&'\u{D7FF}', &'\u{E000}'), Some(1));
+        assert_eq!(
+            char::steps_between(&'\0', &char::MAX),
+            Some(0x10FFFF - 0x800)
+        );
+    }
+
+    #[test]
+    fn should_step_128_bit_integers() {
+        assert_eq!(u128::MAX.forward_checked(1), None);
+        assert_eq!((u128::MAX - 1).forward_checked(1), Some(u128::MAX));
+        assert_eq!(u128::steps_between(&0, &u128::MAX), None);
+        assert_eq!(u128::steps_between(&u128::MAX, &u128::MAX), Some(0));
+        assert_eq!(i128::MIN.backward_checked(1), None);
+        assert_eq!(
+            i128::steps_between_by(&i128::MIN, &(i128::MIN + 10), &5),
+            Some(2)
+        );
+        assert_eq!(i128::steps_between_by(&-5, &5, &-1), None);
+    }
+
+    #[test]
+    fn should_step_non_zero_integers() {
+        let one = NonZeroU32::MIN;
+        assert_eq!(NonZeroU32::unit(), one);
+        assert_eq!(one.backward_checked(1), None);
+        assert_eq!(one.forward_checked(2), NonZeroU32::new(3));
+        assert_eq!(NonZeroU8::MAX.forward_checked(1), None);
+        assert!(NonZeroU64::is_forbidden_zero(" 00"));
+        assert!(!NonZeroU64::is_forbidden_zero("x"));
+        assert!(!u64::is_forbidden_zero("0"));
+    }
+
+    #[test]
+    fn should_skip_zero_for_signed_non_zero_integers() {
+        let (minus_one, one) = (NonZeroI32::new(-1).unwrap(), NonZeroI32::new(1).unwrap());
+        assert_eq!(NonZeroI32::unit(), one);
+        assert_eq!(minus_one.forward_checked(1), Some(one));
+        assert_eq!(one.backward_checked(1), Some(minus_one));
+        assert_eq!(NonZeroI32::steps_between(&minus_one, &one), Some(1));
+        assert_eq!(
+            NonZeroI8::steps_between(&NonZeroI8::MIN, &NonZeroI8::MAX),
+            Some(254)
+        );
+        assert_eq!(NonZeroI8::MIN.backward_checked(1), None);
+        assert_eq!(NonZeroI8::MAX.forward_checked(1), None);
+        assert_eq!(NonZeroI8::MIN.forward_checked(254), Some(NonZeroI8::MAX));
+        let (start, step) = (NonZeroI16::new(-4).unwrap(), NonZeroI16::new(2).unwrap());
+        assert_eq!(start.forward_by(&step, 2), NonZeroI16::new(2));
+        assert_eq!(
+            NonZeroI16::step_dividing(&start, &NonZeroI16::new(4).unwrap(), 3),
+            Some(step)
+        );
+        assert!(NonZeroI64::is_forbidden_zero("-0"));
+    }
+
+    #[test]
+    fn should_step_wrappers_without_wrapping() {
+        assert_eq!(Wrapping(u8::MAX).forward_checked(1), None);
+        assert_eq!(Wrapping(1u8).forward_checked(1), Some(Wrapping(2)));
+        assert_eq!(Saturating(0u8).backward_checked(1), None);
+        assert_eq!(
+            Saturating::<i8>::steps_between(&Saturating(-1), &Saturating(1)),
+            Some(2)
+        );
+        let (start, end, step) = (Wrapping(250u8), Wrapping(u8::MAX), Wrapping(2));
+        let steps = Wrapping::steps_between_by(&start, &end, &step).unwrap();
+        assert_eq!(
+            (0..=steps)
+                .map(|count| start.forward_by(&step, count))
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Wrapping(250)),
+                Some(Wrapping(252)),
+                Some(Wrapping(254))
+            ]
+        );
+        let (start, end) = (Saturating(-2i16), Saturating(1));
+        let steps = Saturating::steps_between(&start, &end).unwrap();
+        assert_eq!(
+            (0..=steps)
+                .map(|count| start.forward_checked(count))
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Saturating(-2)),
+                Some(Saturating(-1)),
+                Some(Saturating(0)),
+                Some(Saturating(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn should_divide_range_into_steps() {
+        assert_eq!(u8::step_dividing(&0, &255, 5), Some(51));
+        assert_eq!(u8::step_dividing(&0, &10, 3), None);
+        assert_eq!(i128::step_dividing(&-10, &10, 4), Some(5));
+        assert_eq!(
+            NonZeroU32::step_dividing(&NonZeroU32::MIN, &NonZeroU32::MIN, 1),
+            None
+        );
+        assert_eq!(f64::step_dividing(&0.0, &1.0, 4), Some(0.25));
+        assert_eq!(char::step_dividing(&'a', &'z', 25), None);
+    }
+
+    #[test]
+    fn should_reach_end_when_dividing_floats() {
+        for count in 1..1000 {
+            let step = f64::step_dividing(&0.0, &1.0, count).unwrap();
+            assert_eq!(f64::steps_between_by(&0.0, &1.0, &step), Some(count));
+            assert!(0.0.forward_by(&step, count).unwrap() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn should_scale_integers() {
+        assert_eq!(3u8.scale_by(&2, 6), Some(192));
+        assert_eq!(3u8.scale_by(&2, 7), None);
+        assert_eq!(u8::scales_between_by(&3, &255, &2), Some(6));
+        assert_eq!(u8::scales_between_by(&0, &255, &2), None);
+        assert_eq!(i64::scales_between_by(&1, &100, &1), None);
+        assert_eq!(u128::MAX.scale_by(&2, 1), None);
+        assert_eq!(u128::scales_between_by(&1, &u128::MAX, &2), Some(127));
+        assert_eq!(u32::factor_dividing(&2, &162, 4), Some(3));
+        assert_eq!(u32::factor_dividing(&1, &100, 3), None);
+        assert_eq!(
+            NonZeroU32::MIN.scale_by(&NonZeroU32::new(5).unwrap(), 2),
+            NonZeroU32::new(25)
+        );
+    }
+
+    #[test]
+    fn should_scale_floats() {
+        assert_eq!(2.0f64.scale_by(&10.0, 3), Some(2000.0));
+        assert_eq!(f64::MAX.scale_by(&2.0, 1), None);
+        assert_eq!(f64::scales_between_by(&1.0, &1000.0, &10.0), Some(3));
+        assert_eq!(f64::scales_between_by(&1.0, &999.0, &10.0), Some(2));
+        assert_eq!(f64::scales_between_by(&-1.0, &10.0, &2.0), None);
+        for count in 1..200 {
+            let factor = f64::factor_dividing(&1.0, &1000.0, count).unwrap();
+            assert_eq!(f64::scales_between_by(&1.0, &1000.0, &factor), Some(count));
+        }
+    }
+
+    #[test]
+    fn should_not_step_chars_by_other_than_unit() {
+        assert_eq!(char::steps_between_by(&'a', &'z', &'b'), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn should_step_decimals_exactly() {
+        use rust_decimal::Decimal;
+
+        let start = Decimal::new(0, 1);
+        let step = Decimal::new(1, 1);
+        assert_eq!(start.forward_by(&step, 3), Some(Decimal::new(3, 1)));
+        assert_eq!(
+            Decimal::steps_between_by(&start, &Decimal::ONE, &step),
+            Some(10)
+        );
+        assert_eq!(
+            Decimal::steps_between_by(&start, &Decimal::ONE, &-step),
+            None
+        );
+        assert_eq!(Decimal::MAX.forward_checked(1), None);
+        assert_eq!(Decimal::MIN.backward_checked(1), None);
+        assert_eq!(
+            Decimal::new(15, 1).scale_by(&Decimal::TEN, 3),
+            Some(Decimal::new(1500, 0))
+        );
+        assert_eq!(
+            Decimal::scales_between_by(&Decimal::ONE, &Decimal::ONE_THOUSAND, &Decimal::TEN),
+            Some(3)
+        );
+    }
+}